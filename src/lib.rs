@@ -80,32 +80,186 @@
 //! ```
 
 use proc_macro::TokenStream;
-use quote::{format_ident, quote};
+use quote::{format_ident, quote, quote_spanned};
+use syn::spanned::Spanned;
 use syn::{
     parse::Parse, parse_macro_input, punctuated::Punctuated, Data, DeriveInput, Fields,
     GenericArgument, Ident, LitStr, PathArguments, Token, Type,
 };
 
+/// Returns the named fields of the struct a derive is applied to, or an error
+/// pointing at the unsupported enum, union or tuple struct.
+fn named_fields<'a>(
+    input: &'a DeriveInput,
+    derive: &str,
+) -> syn::Result<&'a Punctuated<syn::Field, Token![,]>> {
+    match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => Ok(&fields.named),
+            fields => Err(syn::Error::new_spanned(
+                fields,
+                format!("`{}` only supports structs with named fields", derive),
+            )),
+        },
+        Data::Enum(data) => Err(syn::Error::new_spanned(
+            data.enum_token,
+            format!("`{}` only supports structs, not enums", derive),
+        )),
+        Data::Union(data) => Err(syn::Error::new_spanned(
+            data.union_token,
+            format!("`{}` only supports structs, not unions", derive),
+        )),
+    }
+}
+
+/// Returns the struct's generics with a `serde::Serialize` bound added for every
+/// type parameter, as the builder methods serialize values of those types.
+fn with_serialize_bounds(generics: &syn::Generics) -> syn::Generics {
+    let mut generics = generics.clone();
+    let params: Vec<Ident> = generics
+        .type_params()
+        .map(|param| param.ident.clone())
+        .collect();
+    let where_clause = generics.make_where_clause();
+    for param in params {
+        where_clause
+            .predicates
+            .push(syn::parse_quote! { #param: serde::Serialize });
+    }
+    generics
+}
+
 /// Represents MongoDB operations that can be applied to a field.
 /// Used to parse the `#[mongo_ops(...)]` attribute.
+///
+/// Entries are either bare operation keywords (`set`, `push`, ...),
+/// `key = "value"` options refining how the operations are generated, or
+/// `rule("a", "b", ...)` lists used by container-level rules.
+#[derive(Default)]
 struct MongoOps {
-    operations: Vec<String>,
+    operations: Vec<Ident>,
+    options: Vec<(Ident, LitStr)>,
+    lists: Vec<(Ident, Vec<LitStr>)>,
+}
+
+/// Operation keywords accepted in a field's `#[mongo_ops(...)]` attribute.
+const FIELD_OPERATIONS: &[&str] = &[
+    "set",
+    "set_on_insert",
+    "push",
+    "pull",
+    "add_to_set",
+    "inc",
+    "mul",
+    "min",
+    "max",
+    "current_date",
+    "bit",
+    "unset",
+    "none",
+];
+
+/// `key = "value"` options accepted in a field's `#[mongo_ops(...)]` attribute.
+const FIELD_OPTIONS: &[&str] = &[
+    "discriminant",
+    "vec_inner",
+    "transform",
+    "owned",
+    "sub_paths",
+    "tuple_fields",
+    "on_none",
+];
+
+/// `key = "value"` options accepted in the struct's `#[mongo_ops(...)]` attribute.
+const CONTAINER_OPTIONS: &[&str] = &["diff_none", "on_conflict"];
+
+/// `rule("a", "b", ...)` lists accepted in the struct's `#[mongo_ops(...)]` attribute.
+const CONTAINER_LISTS: &[&str] = &["require_together"];
+
+impl MongoOps {
+    /// Returns the value of the given `key = "value"` option, if present.
+    fn option(&self, key: &str) -> Option<&LitStr> {
+        self.options
+            .iter()
+            .find(|(name, _)| name == key)
+            .map(|(_, value)| value)
+    }
+
+    /// Returns every `key("a", "b", ...)` list given for the given key.
+    fn lists<'a>(&'a self, key: &'a str) -> impl Iterator<Item = &'a Vec<LitStr>> + 'a {
+        self.lists
+            .iter()
+            .filter(move |(name, _)| name == key)
+            .map(|(_, values)| values)
+    }
+
+    /// Rejects any keyword that is not in the given allowed sets, so that typos
+    /// are reported instead of silently ignored.
+    fn validate(&self, operations: &[&str], options: &[&str], lists: &[&str]) -> syn::Result<()> {
+        let unknown = |ident: &Ident, kind: &str, allowed: &[&str]| {
+            let expected = if allowed.is_empty() {
+                format!("no {} is supported here", kind)
+            } else {
+                let names: Vec<String> = allowed.iter().map(|name| format!("`{}`", name)).collect();
+                format!("expected one of {}", names.join(", "))
+            };
+            Err(syn::Error::new(
+                ident.span(),
+                format!("unknown `mongo_ops` {} `{}`; {}", kind, ident, expected),
+            ))
+        };
+
+        for ident in &self.operations {
+            if !operations.iter().any(|name| ident == name) {
+                return unknown(ident, "operation", operations);
+            }
+        }
+        for (ident, _) in &self.options {
+            if !options.iter().any(|name| ident == name) {
+                return unknown(ident, "option", options);
+            }
+        }
+        for (ident, _) in &self.lists {
+            if !lists.iter().any(|name| ident == name) {
+                return unknown(ident, "rule", lists);
+            }
+        }
+        Ok(())
+    }
 }
 
 impl Parse for MongoOps {
     fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
-        let operations = Punctuated::<Ident, Token![,]>::parse_terminated(input)?
-            .into_iter()
-            .map(|ident| ident.to_string())
-            .collect();
-        Ok(MongoOps { operations })
+        let mut mongo_ops = MongoOps::default();
+
+        while !input.is_empty() {
+            let ident: Ident = input.parse()?;
+            if input.peek(Token![=]) {
+                input.parse::<Token![=]>()?;
+                mongo_ops.options.push((ident, input.parse()?));
+            } else if input.peek(syn::token::Paren) {
+                let content;
+                syn::parenthesized!(content in input);
+                let values = Punctuated::<LitStr, Token![,]>::parse_terminated(&content)?;
+                mongo_ops.lists.push((ident, values.into_iter().collect()));
+            } else {
+                mongo_ops.operations.push(ident);
+            }
+
+            if input.is_empty() {
+                break;
+            }
+            input.parse::<Token![,]>()?;
+        }
+
+        Ok(mongo_ops)
     }
 }
 
 /// Arguments for the `mongo_nested_fields` attribute macro.
-/// Parses a list of field:type pairs.
+/// Parses a list of field:type pairs, where the type may be omitted.
 struct NestedFieldsArgs {
-    pairs: Vec<(String, String)>,
+    pairs: Vec<(Ident, Option<syn::Path>)>,
 }
 
 impl Parse for NestedFieldsArgs {
@@ -124,30 +278,108 @@ impl Parse for NestedFieldsArgs {
 }
 
 /// Represents a field:type pair for nested field declarations.
+///
+/// The type may be a bare name (`"Address"`) or a full path
+/// (`"crate::models::Address"`) for types defined in other modules. When
+/// omitted, it is taken from the field's declaration.
 struct FieldTypePair {
-    field_name: String,
-    type_name: String,
+    field_name: Ident,
+    type_name: Option<syn::Path>,
 }
 
 impl Parse for FieldTypePair {
     fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
         let field_name: Ident = input.parse()?;
-        input.parse::<Token![:]>()?;
-        let type_name: LitStr = input.parse()?;
+        let type_name = if input.peek(Token![:]) {
+            input.parse::<Token![:]>()?;
+            Some(input.parse::<LitStr>()?.parse()?)
+        } else {
+            None
+        };
 
         Ok(FieldTypePair {
-            field_name: field_name.to_string(),
-            type_name: type_name.value(),
+            field_name,
+            type_name,
         })
     }
 }
 
+/// Returns the path of the nested type stored in a field declared as the type
+/// itself, an `Option`, a `Vec` or a map of it.
+fn nested_type_path(ty: &Type) -> Option<syn::Path> {
+    let ty = get_option_inner_type(ty)
+        .or_else(|| get_vec_inner_type(ty))
+        .unwrap_or(ty);
+    let ty = if is_map_type(ty) {
+        get_map_value_type(ty)?
+    } else {
+        ty
+    };
+    match strip_type_groups(ty) {
+        Type::Path(type_path) if type_path.qself.is_none() => Some(type_path.path.clone()),
+        _ => None,
+    }
+}
+
+/// Returns a closure serializing an array element of `inner_type`,
+/// spanned at the type so that a missing `Serialize` implementation is reported
+/// once, at the field, for all the methods generated for it.
+fn element_serializer(inner_type: &Type) -> proc_macro2::TokenStream {
+    quote_spanned! { inner_type.span() => (|value: &#inner_type| Self::element_to_bson::<#inner_type>(value)) }
+}
+
+/// Generates an expression serializing the elements of `values`, a slice of
+/// `inner_type`, to a BSON array.
+fn elements_bson(values: proc_macro2::TokenStream, inner_type: &Type) -> proc_macro2::TokenStream {
+    let serializer = element_serializer(inner_type);
+    quote! {
+        bson::Bson::Array(#values.iter().map(#serializer).collect::<Result<Vec<_>, _>>()?)
+    }
+}
+
+/// Returns the path of the update builder generated for the given type path,
+/// e.g. `crate::models::AddressUpdateBuilder` for `crate::models::Address`.
+fn builder_path(type_path: &syn::Path) -> syn::Path {
+    let mut builder = type_path.clone();
+    if let Some(segment) = builder.segments.last_mut() {
+        segment.ident = format_ident!("{}UpdateBuilder", segment.ident);
+    }
+    builder
+}
+
+/// Removes the invisible groups wrapping types substituted by `macro_rules!`,
+/// as well as redundant parentheses, so detection sees the underlying type.
+fn strip_type_groups(ty: &Type) -> &Type {
+    match ty {
+        Type::Group(group) => strip_type_groups(&group.elem),
+        Type::Paren(paren) => strip_type_groups(&paren.elem),
+        _ => ty,
+    }
+}
+
 /// Returns the inner type if the type is a Vec<T>.
 /// Used to support operations on array fields.
 fn get_vec_inner_type(ty: &Type) -> Option<&Type> {
-    if let Type::Path(type_path) = ty {
+    get_wrapped_type(ty, "Vec")
+}
+
+/// Returns the inner type if the type is an Option<T>.
+fn get_option_inner_type(ty: &Type) -> Option<&Type> {
+    get_wrapped_type(ty, "Option")
+}
+
+/// Returns the inner type if the type is a Box<T>.
+fn get_box_inner_type(ty: &Type) -> Option<&Type> {
+    get_wrapped_type(ty, "Box")
+}
+
+/// Returns the type argument of a single-parameter wrapper such as `Vec<T>`,
+/// matching on the last path segment so qualified paths (`std::vec::Vec<T>`,
+/// `alloc::vec::Vec<T>`) are recognized too.
+fn get_wrapped_type<'a>(ty: &'a Type, wrapper: &str) -> Option<&'a Type> {
+    if let Type::Path(type_path) = strip_type_groups(ty) {
         if let Some(segment) = type_path.path.segments.last() {
-            if segment.ident == "Vec" {
+            if segment.ident == wrapper {
                 if let PathArguments::AngleBracketed(args) = &segment.arguments {
                     if let Some(GenericArgument::Type(inner_type)) = args.args.first() {
                         return Some(inner_type);
@@ -159,6 +391,470 @@ fn get_vec_inner_type(ty: &Type) -> Option<&Type> {
     None
 }
 
+/// Returns the first generic type argument of the type, e.g. `T` for `Localized<T>`.
+/// Used to type the setters generated for a wrapper's sub-paths.
+fn get_first_type_argument(ty: &Type) -> Option<&Type> {
+    if let Type::Path(type_path) = strip_type_groups(ty) {
+        if let Some(segment) = type_path.path.segments.last() {
+            if let PathArguments::AngleBracketed(args) = &segment.arguments {
+                return args.args.iter().find_map(|arg| match arg {
+                    GenericArgument::Type(inner_type) => Some(inner_type),
+                    _ => None,
+                });
+            }
+        }
+    }
+    None
+}
+
+/// Returns the value type of a `HashMap<K, V>` or `BTreeMap<K, V>`.
+fn get_map_value_type(ty: &Type) -> Option<&Type> {
+    if let Type::Path(type_path) = strip_type_groups(ty) {
+        if let Some(segment) = type_path.path.segments.last() {
+            if let PathArguments::AngleBracketed(args) = &segment.arguments {
+                return args
+                    .args
+                    .iter()
+                    .filter_map(|arg| match arg {
+                        GenericArgument::Type(inner_type) => Some(inner_type),
+                        _ => None,
+                    })
+                    .nth(1);
+            }
+        }
+    }
+    None
+}
+
+/// Returns true if the type is one of Rust's primitive integer types.
+/// Used to generate overflow-checked increments.
+fn is_integer_type(ty: &Type) -> bool {
+    const INTEGER_TYPES: &[&str] = &[
+        "i8", "i16", "i32", "i64", "i128", "isize", "u8", "u16", "u32", "u64", "u128", "usize",
+    ];
+    match strip_type_groups(ty) {
+        Type::Path(type_path) => type_path
+            .path
+            .get_ident()
+            .is_some_and(|ident| INTEGER_TYPES.iter().any(|name| ident == name)),
+        _ => false,
+    }
+}
+
+/// Returns true if the type is a `HashMap` or `BTreeMap`.
+/// Used to generate keyed updates for maps of sub-documents.
+fn is_map_type(ty: &Type) -> bool {
+    match strip_type_groups(ty) {
+        Type::Path(type_path) => type_path
+            .path
+            .segments
+            .last()
+            .is_some_and(|segment| segment.ident == "HashMap" || segment.ident == "BTreeMap"),
+        _ => false,
+    }
+}
+
+/// Returns true if the type is `String`, `str` or a reference to one of them.
+/// Used to generate `regex_<field>` filter methods.
+fn is_string_type(ty: &Type) -> bool {
+    match strip_type_groups(ty) {
+        Type::Path(type_path) => type_path
+            .path
+            .segments
+            .last()
+            .is_some_and(|segment| segment.ident == "String" || segment.ident == "str"),
+        Type::Reference(reference) => is_string_type(&reference.elem),
+        _ => false,
+    }
+}
+
+/// Returns true if the type is `std::time::SystemTime` or an `Option` of it.
+/// Used to store timestamps as BSON dates.
+fn is_system_time_type(ty: &Type) -> bool {
+    if let Some(inner) = get_option_inner_type(ty) {
+        return is_system_time_type(inner);
+    }
+    match strip_type_groups(ty) {
+        Type::Path(type_path) => type_path
+            .path
+            .segments
+            .last()
+            .is_some_and(|segment| segment.ident == "SystemTime"),
+        _ => false,
+    }
+}
+
+/// Converts `value`, a `SystemTime` or `Option<SystemTime>` of type `ty`, to the
+/// BSON date it is stored as, with `None` stored as null.
+fn system_time_bson(value: proc_macro2::TokenStream, ty: &Type) -> proc_macro2::TokenStream {
+    if get_option_inner_type(ty).is_some() {
+        quote! {
+            (#value).map_or(bson::Bson::Null, |time| {
+                bson::Bson::DateTime(bson::DateTime::from_system_time(time))
+            })
+        }
+    } else {
+        quote! { bson::Bson::DateTime(bson::DateTime::from_system_time(#value)) }
+    }
+}
+
+/// Returns the `serialize` name given by a `serde` attribute item such as
+/// `rename = "x"` or `rename(serialize = "x")`, or `None` if the attributes
+/// have no such item.
+fn serde_name(attrs: &[syn::Attribute], item: &str) -> Option<String> {
+    // Consumes an item's value, if any, so that parsing can continue
+    fn skip(meta: &syn::meta::ParseNestedMeta) -> syn::Result<()> {
+        if meta.input.peek(Token![=]) {
+            meta.value()?.parse::<syn::Expr>()?;
+        } else if meta.input.peek(syn::token::Paren) {
+            meta.parse_nested_meta(|nested| skip(&nested))?;
+        }
+        Ok(())
+    }
+
+    let mut name = None;
+    for attr in attrs.iter().filter(|attr| attr.path().is_ident("serde")) {
+        let _ = attr.parse_nested_meta(|meta| {
+            if !meta.path.is_ident(item) {
+                return skip(&meta);
+            }
+            if meta.input.peek(Token![=]) {
+                name = Some(meta.value()?.parse::<LitStr>()?.value());
+                return Ok(());
+            }
+            meta.parse_nested_meta(|nested| {
+                if nested.path.is_ident("serialize") {
+                    name = Some(nested.value()?.parse::<LitStr>()?.value());
+                    Ok(())
+                } else {
+                    skip(&nested)
+                }
+            })
+        });
+    }
+    name
+}
+
+/// Applies a `serde` `rename_all` rule to a snake_case field name, the same
+/// way serde does.
+fn apply_rename_all(rule: &str, field: &str) -> String {
+    let pascal = || {
+        field
+            .split('_')
+            .map(|word| {
+                let mut chars = word.chars();
+                match chars.next() {
+                    Some(first) => first.to_ascii_uppercase().to_string() + chars.as_str(),
+                    None => String::new(),
+                }
+            })
+            .collect::<String>()
+    };
+    match rule {
+        "UPPERCASE" | "SCREAMING_SNAKE_CASE" => field.to_ascii_uppercase(),
+        "PascalCase" => pascal(),
+        "camelCase" => {
+            let pascal = pascal();
+            let mut chars = pascal.chars();
+            match chars.next() {
+                Some(first) => first.to_ascii_lowercase().to_string() + chars.as_str(),
+                None => String::new(),
+            }
+        }
+        "kebab-case" => field.replace('_', "-"),
+        "SCREAMING-KEBAB-CASE" => field.to_ascii_uppercase().replace('_', "-"),
+        _ => field.to_string(),
+    }
+}
+
+/// Returns the BSON key of a named field: its `serde` rename if any, else the
+/// field name with the container's `rename_all` rule applied.
+fn serde_field_key(field: &syn::Field, rename_all: Option<&str>) -> String {
+    use syn::ext::IdentExt;
+
+    if let Some(rename) = serde_name(&field.attrs, "rename") {
+        return rename;
+    }
+    let name = field.ident.as_ref().unwrap().unraw().to_string();
+    match rename_all {
+        Some(rule) => apply_rename_all(rule, &name),
+        None => name,
+    }
+}
+
+/// Returns the named field, if the struct has one.
+fn find_field<'a>(input: &'a DeriveInput, field_name: &str) -> Option<&'a syn::Field> {
+    match &input.data {
+        Data::Struct(data) => data.fields.iter().find(|field| {
+            field
+                .ident
+                .as_ref()
+                .is_some_and(|ident| ident == field_name)
+        }),
+        _ => None,
+    }
+}
+
+/// Returns the declared type of the named field, if the struct has one.
+fn find_field_type<'a>(input: &'a DeriveInput, field_name: &str) -> Option<&'a Type> {
+    find_field(input, field_name).map(|field| &field.ty)
+}
+
+/// Wraps the conversion staging the given document key so that a failure to
+/// serialize its value is either returned or, for `build_lossy`, collected.
+fn fallible_conversion(
+    key: &str,
+    conversion: proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
+    quote! {
+        let staged: Result<(), mongodb::error::Error> = (|| {
+            #conversion
+            Ok(())
+        })();
+        if let Err(error) = staged {
+            match failures.as_deref_mut() {
+                Some(failures) => failures.push((#key.to_string(), error)),
+                None => return Err(error),
+            }
+        }
+    }
+}
+
+/// Generates an expression converting the tuple `value` to a BSON sub-document
+/// whose keys are the given element names, in order.
+fn tuple_document(value: proc_macro2::TokenStream, names: &[String]) -> proc_macro2::TokenStream {
+    let indices = (0..names.len()).map(syn::Index::from);
+    quote! {
+        {
+            let mut tuple = bson::Document::new();
+            #(tuple.insert(#names, bson::to_bson(&#value.#indices)?);)*
+            bson::Bson::Document(tuple)
+        }
+    }
+}
+
+/// Generates the `diff` staging for a field, comparing the BSON forms of the
+/// old and new values and setting the field to the new one when they differ.
+///
+/// An `Option` field that became `None` is unset instead, or set to null when
+/// `none_as_null` is given. Fields stored by `discriminant` are compared and set
+/// as their integer discriminant.
+fn diff_staging(
+    field_name: &Ident,
+    field_key: &str,
+    field_type: &Type,
+    none_as_null: bool,
+    tuple_fields: Option<&[String]>,
+    discriminant: Option<&Type>,
+) -> proc_macro2::TokenStream {
+    let value_of = |source: proc_macro2::TokenStream| {
+        if let Some(repr) = discriminant {
+            quote! { bson::to_bson(&(#source.#field_name.clone() as #repr))? }
+        } else if let Some(names) = tuple_fields {
+            tuple_document(quote! { #source.#field_name }, names)
+        } else if is_system_time_type(field_type) {
+            system_time_bson(quote! { #source.#field_name }, field_type)
+        } else {
+            quote! { bson::to_bson(&#source.#field_name)? }
+        }
+    };
+    let old_value = value_of(quote! { old });
+    let new_value = value_of(quote! { new });
+    let stage_none = if none_as_null {
+        quote! { builder.path_updates.insert(#field_key.to_string(), bson::Bson::Null); }
+    } else {
+        quote! { builder.unset_paths.push(#field_key.to_string()); }
+    };
+    let stage = if get_option_inner_type(field_type).is_some() {
+        quote! {
+            if new.#field_name.is_none() {
+                #stage_none
+            } else {
+                builder.path_updates.insert(#field_key.to_string(), new_value);
+            }
+        }
+    } else {
+        quote! { builder.path_updates.insert(#field_key.to_string(), new_value); }
+    };
+    quote! {
+        let new_value = #new_value;
+        if #old_value != new_value {
+            #stage
+        }
+    }
+}
+
+/// How the values of a settable field are taken by its setters, staged by the
+/// builder and converted to BSON.
+///
+/// Shared by `set` and `set_on_insert`, so that a document inserted by an upsert
+/// stores the field in the same shape as an update does.
+struct SetterValue {
+    /// Generic parameters of the setter, used by `owned` borrowed inputs.
+    generics: proc_macro2::TokenStream,
+    /// Where clause of the setter, bounding its generic parameters.
+    where_clause: proc_macro2::TokenStream,
+    /// Type of the setter's `value` argument.
+    param: proc_macro2::TokenStream,
+    /// Statements run first, turning `value` into the value recorded in the history.
+    prepare: proc_macro2::TokenStream,
+    /// Type of the staged value.
+    storage: proc_macro2::TokenStream,
+    /// Expression staging `value` after its `transform` and `discriminant` cast.
+    stage: proc_macro2::TokenStream,
+    /// Expression converting `value`, a reference to the staged value, to BSON.
+    convert: proc_macro2::TokenStream,
+    /// Whether `None` may be staged, for `Option<T>` and `Option<Box<T>>` fields.
+    nullable: bool,
+}
+
+/// Returns how the values of a field are set, from its type and its
+/// `discriminant`, `owned`, `transform` and `tuple_fields` options.
+fn setter_value(
+    field_type: &Type,
+    discriminant: Option<&Type>,
+    owned_type: Option<&Type>,
+    transform: Option<&syn::Path>,
+    tuple_fields: Option<&[String]>,
+) -> syn::Result<SetterValue> {
+    let transformed = match transform {
+        Some(transform) => quote! { #transform(&value) },
+        None => quote! { value },
+    };
+    let boxed_type = get_option_inner_type(field_type).and_then(get_box_inner_type);
+    if let (Some(owned_type), true) = (owned_type, discriminant.is_some() || boxed_type.is_some()) {
+        return Err(syn::Error::new_spanned(
+            owned_type,
+            "`owned` cannot be combined with `discriminant` or an `Option<Box<T>>` field",
+        ));
+    }
+
+    let mut value = SetterValue {
+        generics: quote! {},
+        where_clause: quote! {},
+        param: quote! { #field_type },
+        prepare: quote! {},
+        storage: quote! { #field_type },
+        stage: transformed.clone(),
+        convert: quote! { bson::to_bson(value)? },
+        nullable: false,
+    };
+    if let Some(repr) = discriminant {
+        // Fieldless enums are stored as their integer discriminant
+        value.storage = quote! { #repr };
+        value.stage = quote! { #transformed as #repr };
+    } else if let Some(boxed_type) = boxed_type {
+        // `Option<Box<T>>` takes `T` directly, stored behind an `Arc` so the
+        // builder stays `Clone` even when `T` isn't
+        value.param = quote! { #boxed_type };
+        value.storage = quote! { Option<std::sync::Arc<#boxed_type>> };
+        value.stage = quote! { Some(std::sync::Arc::new(#transformed)) };
+        value.convert = quote! {
+            match value {
+                Some(value) => bson::to_bson(value.as_ref())?,
+                None => bson::Bson::Null,
+            }
+        };
+        value.nullable = true;
+    } else {
+        let storage_type = owned_type.unwrap_or(field_type);
+        // `SystemTime` is stored as a BSON date rather than serde's struct form,
+        // and tuples may be stored as named sub-documents instead of arrays
+        if let Some(names) = tuple_fields {
+            value.convert = tuple_document(quote! { value }, names);
+        } else if is_system_time_type(storage_type) {
+            value.convert = system_time_bson(quote! { *value }, storage_type);
+        }
+        if let Some(owned_type) = owned_type {
+            // An owned storage type decouples the setter from borrowed field types
+            value.generics = quote! { <Borrowed> };
+            value.where_clause = quote! {
+                where
+                    Borrowed: ?Sized + ToOwned<Owned = #owned_type>,
+            };
+            value.param = quote! { &Borrowed };
+            value.prepare = quote! { let value = value.to_owned(); };
+            value.storage = quote! { #owned_type };
+        } else if let Some(option_inner) = get_option_inner_type(field_type) {
+            // `Option<T>` fields take `T`, with null staged by a separate method
+            value.param = quote! { #option_inner };
+            value.stage = quote! { Some(#transformed) };
+            value.nullable = true;
+        }
+    }
+    Ok(value)
+}
+
+/// Generates the body of an `apply`-style method calling the given driver method.
+///
+/// With the `tracing` feature enabled, the call is wrapped in a span named
+/// `Struct::operation` recording the target collection and the number of changes.
+/// The feature is resolved once for the whole build, so enabling it anywhere
+/// makes the code derived in every crate refer to `tracing`.
+fn apply_body(
+    name: &Ident,
+    operation: &str,
+    driver_method: proc_macro2::TokenStream,
+    upsert: bool,
+) -> proc_macro2::TokenStream {
+    let record_changes = if cfg!(feature = "tracing") {
+        quote! {
+            let changes: usize = update
+                .values()
+                .filter_map(|value| value.as_document())
+                .map(|operator_doc| operator_doc.len())
+                .sum();
+            span.record("changes", changes);
+        }
+    } else {
+        quote! {}
+    };
+
+    let (prepare_builder, prepare_upsert) = if upsert {
+        (
+            quote! {
+                let mut builder = self;
+                builder.for_upsert = true;
+            },
+            quote! {
+                options.upsert = Some(true);
+            },
+        )
+    } else {
+        (quote! { let builder = self; }, quote! {})
+    };
+
+    let body = quote! {
+        #prepare_builder
+        let (update, array_filters) = builder.build_with_options()?;
+        #record_changes
+
+        let mut options = mongodb::options::UpdateOptions::default();
+        #prepare_upsert
+        if !array_filters.is_empty() {
+            options.array_filters = Some(array_filters);
+        }
+        collection.#driver_method(filter, update, options).await
+    };
+
+    if cfg!(feature = "tracing") {
+        let span_name = format!("{}::{}", name, operation);
+        quote! {
+            use tracing::Instrument;
+            let span = tracing::info_span!(
+                #span_name,
+                collection = collection.name(),
+                changes = tracing::field::Empty
+            );
+            // Recorded on the span itself, which needn't be tracked as current
+            let instrumented = span.clone();
+            async move { #body }.instrument(instrumented).await
+        }
+    } else {
+        body
+    }
+}
+
 /// A derive macro that generates an update builder for a struct.
 ///
 /// The update builder provides methods for creating MongoDB update operations
@@ -166,11 +862,118 @@ fn get_vec_inner_type(ty: &Type) -> Option<&Type> {
 ///
 /// # Supported Operations
 ///
-/// - `set`: Generate methods for setting field values (default if no operations specified)
-/// - `push`: Generate methods for pushing to array fields (Vec types only)
-/// - `pull`: Generate methods for pulling from array fields (Vec types only)
+/// - `set`: Generate methods for setting field values (default if no operations specified).
+///   For `Option<T>` fields the setter takes `T` and a `set_<field>_null` method stages
+///   `None`, leaving the field untouched unless one of them is called. For
+///   `Option<Box<T>>` fields the setter takes `T` and a `clear_<field>` method sets null.
+///   `std::time::SystemTime` values, optional or not, are stored as BSON dates with
+///   millisecond precision; times before the Unix epoch become negative dates, and times
+///   beyond the BSON range are clamped to its bounds
+/// - `set_on_insert`: Generate `set_on_insert_<field>` methods emitting `$setOnInsert`,
+///   applied only when an upsert inserts a new document. They take and store values
+///   the same way as `set_<field>`, following the options below. May be combined with `set`
+/// - `push`: Generate methods for pushing to array fields (Vec types only), appending
+///   one (`push_<field>`) or several (`push_many_<field>`) values to `$each`, a
+///   `push_<field>_with` variant taking a generated `<Struct>PushOptions` with the
+///   `$position`, `$slice` and `$sort` modifiers, and a `sort_<field>` re-sorting the
+///   array in place
+/// - `pull`: Generate methods for pulling from array fields (Vec types only), including
+///   `pull_<field>_lt`/`pull_<field>_gt` removing the elements below or above a bound,
+///   `pull_<field>_where` removing the elements matching a condition document (not to be
+///   combined with the other `$pull` methods), and `pull_all_<field>` removing several
+///   values with `$pullAll`
+/// - `add_to_set`: Generate `add_to_set_<field>` methods adding values to array fields
+///   (Vec types only) with `$addToSet` and `$each`, skipping values already present
+/// - `inc`: Generate `inc_<field>` methods emitting `$inc`, plus an overflow-checked
+///   `inc_<field>_checked` for integer fields. Non-numeric fields are rejected at
+///   compile time
+/// - `mul`: Generate `mul_<field>` methods emitting `$mul`, multiplying the field by
+///   a factor of the field's type. Non-numeric fields are rejected at compile time
+/// - `bit`: Generate `bit_and_<field>`, `bit_or_<field>` and `bit_xor_<field>` methods
+///   emitting `$bit`. Only one bitwise operation per field is kept, the last one
+///   staged. Only integers stored as BSON `int` or `long` (`i8` to `i64`, `u8` to
+///   `u32`) are accepted, other fields are rejected at compile time
+/// - `min`/`max`: Generate `min_<field>`/`max_<field>` methods emitting `$min`/`$max`,
+///   which only update the field when the value is lower or higher than the stored one.
+///   Fields stored by `discriminant` are compared as their integer discriminant
+/// - `current_date`: Generate `set_<field>_now`/`set_<field>_timestamp` methods emitting
+///   `$currentDate`, stamping the server's time as a date or a BSON timestamp
+/// - `unset`: Generate `unset_<field>` methods removing the field with `$unset`; of an
+///   unset and a setter call for the same field, the later one wins
 /// - `none`: Exclude the field from the update builder
 ///
+/// Unknown operations and options are compile errors listing the accepted names.
+///
+/// Settable fields, other than `SystemTime` ones, `tuple_fields` tuples and those
+/// stored by `discriminant` or as `Option<Box<T>>`, also get a `set_<field>_pre` taking a
+/// generated `<Struct>PreSerialized<T>`, a value serialized once and reusable
+/// across builders.
+///
+/// # Options
+///
+/// - `discriminant = "i32"`: Store a fieldless enum as its integer discriminant
+///   (any integer type may be given) instead of serializing it through serde. The
+///   enum must implement `Clone` for `diff` to compare discriminants.
+///   Without it, enum fields are serialized like any other value, unit variants
+///   becoming their (renamed) names; variants serde skips make the build fail
+/// - `vec_inner = "T"`: Element type used by `push`/`pull` when the field's `Vec`
+///   is hidden behind a type alias
+/// - `transform = "path::to::fn"`: Normalize values passed to `set_<field>` and
+///   `set_on_insert_<field>` with a function of signature `fn(&T) -> T` before staging
+///   them, e.g. `str::to_lowercase`
+/// - `owned = "T"`: Store values as the owned type `T` and make `set_<field>` accept
+///   any borrowed `&B` where `B: ToOwned<Owned = T>` (e.g. `&str` for `String`),
+///   regardless of the field's own, possibly borrowed, type
+/// - `sub_paths = "en, fr"`: Generate `set_<field>_<sub_path>` setters for the
+///   listed sub-paths of a generic wrapper type such as `Localized<T>`, each
+///   taking the wrapper's first type argument
+/// - `tuple_fields = "lng, lat"`: Store a tuple field as a sub-document with the
+///   given key for each element (`{ "lng": .., "lat": .. }`) instead of serde's array
+/// - `on_none = "null" | "unset" | "skip"`: Choose what `set_<field>_null()`, or
+///   `clear_<field>()` for `Option<Box<T>>` fields, stages: a null value (the
+///   default), an `$unset` of the field, or nothing at all
+///
+/// Options are honored for every kind of field they are given on, or rejected at
+/// compile time: `owned` cannot be combined with `discriminant` or `Option<Box<T>>`
+/// fields, `on_none` requires an `Option` field, and each option requires one of the
+/// operations it refines (`set`, plus `set_on_insert` for all but `on_none`, and
+/// `min`/`max` for `discriminant`).
+///
+/// # Container Options
+///
+/// - `#[mongo_ops(require_together("a", "b", ...))]` on the struct: building the
+///   update fails when some, but not all, of the listed fields are staged, under
+///   any operator. The attribute may be repeated for independent groups
+/// - `#[mongo_ops(diff_none = "null")]` on the struct: make `diff` set `Option`
+///   fields that became `None` to null instead of unsetting them (`"unset"`)
+/// - `#[mongo_ops(on_conflict = "error")]` on the struct: treat staging the same
+///   `$set` path twice as an error instead of keeping the last value (`"last_wins"`,
+///   the default). `set_field`, `set_root` and the direct nested path methods fail
+///   immediately, while the typed setters and nested builder methods record the
+///   conflict and make the build fail
+///
+/// # Limitations
+///
+/// Array fields are detected syntactically from the `Vec<T>` type, so a field
+/// declared through an alias such as `type Tags = Vec<String>` needs
+/// `vec_inner = "String"` for its `push`/`pull` methods to be generated.
+///
+/// Attributes wrapped in `cfg_attr` are expanded by the compiler before the derive
+/// runs, so `#[cfg_attr(feature = "x", mongo_ops(set, push))]` behaves like the
+/// plain attribute whenever the predicate holds; the same goes for `serde` renames.
+///
+/// Document keys follow `serde`: a field's `rename` (or its `serialize` name) is
+/// used when present, otherwise the container's `rename_all` rule is applied to the
+/// field name. Method names always follow the Rust field names.
+///
+/// Generic structs and lifetime parameters are supported: the builder carries the
+/// struct's parameters (`Page::<String>::update_builder()`), and its methods require
+/// every type parameter to implement `Serialize`.
+///
+/// The generated code grows linearly with the number of fields and expands no
+/// recursive macros per field, so wide structs (well over 100 fields) derive
+/// without raising `recursion_limit`.
+///
 /// # Example
 ///
 /// ```rust
@@ -190,114 +993,1214 @@ pub fn derive_mongo_update_builder(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
     let name = &input.ident;
     let builder_name = format_ident!("{}UpdateBuilder", name);
+    let generics = &input.generics;
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let serialize_generics = with_serialize_bounds(generics);
+    let serialize_where_clause = &serialize_generics.where_clause;
 
-    let fields = match input.data {
-        Data::Struct(ref data) => match data.fields {
-            Fields::Named(ref fields) => &fields.named,
-            _ => panic!("Only named fields are supported"),
-        },
-        _ => panic!("Only structs are supported"),
+    let fields = match named_fields(&input, "MongoOperations") {
+        Ok(fields) => fields,
+        Err(err) => return err.to_compile_error().into(),
     };
 
+    let push_options_name = format_ident!("{}PushOptions", name);
+    let frozen_update_name = format_ident!("{}FrozenUpdate", name);
+    let pre_serialized_name = format_ident!("{}PreSerialized", name);
+    let staged_op_name = format_ident!("{}StagedOp", name);
+    let mut has_push = false;
+
     let mut builder_methods = Vec::new();
     let mut builder_fields = Vec::new();
     let mut set_conversions = Vec::new();
     let mut push_conversions = Vec::new();
     let mut pull_conversions = Vec::new();
+    let mut add_to_set_conversions = Vec::new();
+    let mut inc_conversions = Vec::new();
+    let mut mul_conversions = Vec::new();
+    let mut bit_conversions = Vec::new();
+    let mut bound_conversions = Vec::new();
+    let mut insert_conversions = Vec::new();
+    let mut current_date_conversions = Vec::new();
+    let mut unset_conversions = Vec::new();
+    let mut numeric_assertions = Vec::new();
+    let mut bitwise_assertions = Vec::new();
+    let mut described: Vec<(String, &str)> = Vec::new();
+    let mut diff_stagings = Vec::new();
+    let mut merge_stagings = Vec::new();
+
+    // Document keys follow serde's field renames
+    let rename_all = serde_name(&input.attrs, "rename_all");
+
+    // Container-level rules checked when the update is built
+    let mut container_ops = MongoOps::default();
+    for attr in &input.attrs {
+        if attr.path().is_ident("mongo_ops") {
+            let parsed = match attr.parse_args::<MongoOps>().and_then(|parsed| {
+                parsed.validate(&[], CONTAINER_OPTIONS, CONTAINER_LISTS)?;
+                Ok(parsed)
+            }) {
+                Ok(parsed) => parsed,
+                Err(err) => return err.to_compile_error().into(),
+            };
+            container_ops.operations.extend(parsed.operations);
+            container_ops.options.extend(parsed.options);
+            container_ops.lists.extend(parsed.lists);
+        }
+    }
+    // Array elements are matched by their `id` field, `_id` being accepted too
+    let id_key = fields
+        .iter()
+        .find(|field| {
+            field
+                .ident
+                .as_ref()
+                .is_some_and(|ident| ident == "id" || ident == "_id")
+        })
+        .map_or_else(
+            || "id".to_string(),
+            |field| serde_field_key(field, rename_all.as_deref()),
+        );
+
+    let mut rule_checks = Vec::new();
+    for group in container_ops.lists("require_together") {
+        let mut group_keys = Vec::new();
+        for group_lit in group {
+            let group_field = group_lit.value();
+            let Some(field) = fields.iter().find(|field| {
+                field
+                    .ident
+                    .as_ref()
+                    .is_some_and(|ident| *ident == group_field)
+            }) else {
+                return syn::Error::new(
+                    group_lit.span(),
+                    format!(
+                        "require_together references unknown field `{}`",
+                        group_field
+                    ),
+                )
+                .to_compile_error()
+                .into();
+            };
+            group_keys.push(serde_field_key(field, rename_all.as_deref()));
+        }
+        rule_checks.push(quote! {
+            let group: &[&str] = &[#(#group_keys),*];
+            let staged = group.iter().find(|field| is_staged(&update, field));
+            let missing = group.iter().find(|field| !is_staged(&update, field));
+            if let (Some(staged), Some(missing)) = (staged, missing) {
+                let error = mongodb::error::Error::custom(format!(
+                    "`{}` must be updated together with `{}`",
+                    missing, staged
+                ));
+                return match failures {
+                    Some(failures) => {
+                        failures.push(((*missing).to_string(), error));
+                        Ok((Document::new(), Vec::new()))
+                    }
+                    None => Err(error),
+                };
+            }
+        });
+    }
+    let rule_checks = if rule_checks.is_empty() {
+        quote! {}
+    } else {
+        quote! {
+            fn is_staged(update: &Document, field: &str) -> bool {
+                let prefix = format!("{}.", field);
+                update.values().any(|operator| match operator {
+                    bson::Bson::Document(paths) => paths
+                        .keys()
+                        .any(|path| path == field || path.starts_with(&prefix)),
+                    _ => false,
+                })
+            }
+
+            #(#rule_checks)*
+        }
+    };
+
+    // Fields that became `None` are unset by `diff` unless configured otherwise
+    let diff_none_as_null = match container_ops.option("diff_none") {
+        Some(lit) if lit.value() == "null" => true,
+        Some(lit) if lit.value() == "unset" => false,
+        Some(lit) => {
+            return syn::Error::new(lit.span(), "expected `diff_none = \"unset\"` or `\"null\"`")
+                .to_compile_error()
+                .into()
+        }
+        None => false,
+    };
+
+    // Staging the same path twice either overwrites it or is reported as an error
+    let on_conflict_error = match container_ops.option("on_conflict") {
+        Some(lit) if lit.value() == "error" => true,
+        Some(lit) if lit.value() == "last_wins" => false,
+        Some(lit) => {
+            return syn::Error::new(
+                lit.span(),
+                "expected `on_conflict = \"error\"` or `\"last_wins\"`",
+            )
+            .to_compile_error()
+            .into()
+        }
+        None => false,
+    };
+    let conflict_check_for = |storage: &Ident, path: &str| {
+        if on_conflict_error {
+            quote! {
+                if self.#storage.is_some() || self.path_updates.contains_key(#path) {
+                    self.conflicts.push(#path.to_string());
+                }
+            }
+        } else {
+            quote! {}
+        }
+    };
+    let path_key_conflict_check = |path: &str| {
+        if on_conflict_error {
+            quote! {
+                if self.path_updates.contains_key(#path) {
+                    self.conflicts.push(#path.to_string());
+                }
+            }
+        } else {
+            quote! {}
+        }
+    };
+    let many_conflict_check = if on_conflict_error {
+        quote! {
+            if self.path_updates.contains_key(*field_path) {
+                self.conflicts.push(field_path.to_string());
+            }
+        }
+    } else {
+        quote! {}
+    };
+    let merge_conflict_check = if on_conflict_error {
+        quote! {
+            self.conflicts.extend(other.conflicts);
+            for path in other.path_updates.keys() {
+                if self.path_updates.contains_key(path) {
+                    self.conflicts.push(path.clone());
+                }
+            }
+        }
+    } else {
+        quote! {}
+    };
+    let nested_conflict_check = if on_conflict_error {
+        quote! {
+            if self.path_updates.contains_key(&path) {
+                self.conflicts.push(path.clone());
+            }
+        }
+    } else {
+        quote! {}
+    };
+    let path_conflict_check = if on_conflict_error {
+        quote! {
+            if self.path_updates.contains_key(field_path) {
+                return Err(mongodb::error::Error::custom(format!(
+                    "`{}` is staged more than once",
+                    field_path
+                )));
+            }
+        }
+    } else {
+        quote! {}
+    };
 
     // Process all fields
     for field in fields.iter() {
         let field_name = field.ident.as_ref().unwrap();
         let field_type = &field.ty;
 
-        let mut ops = vec![];
+        let mut mongo_ops = MongoOps::default();
         for attr in &field.attrs {
             if attr.path().is_ident("mongo_ops") {
-                if let Ok(mongo_ops) = attr.parse_args::<MongoOps>() {
-                    ops = mongo_ops.operations;
-                }
+                mongo_ops = match attr.parse_args::<MongoOps>().and_then(|parsed| {
+                    parsed.validate(FIELD_OPERATIONS, FIELD_OPTIONS, &[])?;
+                    Ok(parsed)
+                }) {
+                    Ok(parsed) => parsed,
+                    Err(err) => return err.to_compile_error().into(),
+                };
             }
         }
+        let ops: Vec<String> = mongo_ops.operations.iter().map(Ident::to_string).collect();
 
         if ops.contains(&"none".to_string()) {
             continue;
         }
 
-        let field_name_str = field_name.to_string();
+        let field_key = serde_field_key(field, rename_all.as_deref());
+
+        // Value options only refine the operations staging the field's value
+        let value_options: [(&str, &[&str]); 5] = [
+            ("discriminant", &["set", "set_on_insert", "min", "max"]),
+            ("transform", &["set", "set_on_insert"]),
+            ("owned", &["set", "set_on_insert"]),
+            ("tuple_fields", &["set", "set_on_insert"]),
+            ("on_none", &["set"]),
+        ];
+        for (key, operations) in value_options {
+            let applies =
+                ops.is_empty() || operations.iter().any(|op| ops.contains(&op.to_string()));
+            if let (Some(lit), false) = (mongo_ops.option(key), applies) {
+                let names: Vec<String> = operations.iter().map(|op| format!("`{}`", op)).collect();
+                return syn::Error::new(
+                    lit.span(),
+                    format!(
+                        "`{}` requires one of the {} operations",
+                        key,
+                        names.join(", ")
+                    ),
+                )
+                .to_compile_error()
+                .into();
+            }
+        }
+
+        // Fieldless enums can be stored as their integer discriminant
+        let discriminant = match mongo_ops.option("discriminant").map(LitStr::parse::<Type>) {
+            Some(Ok(repr)) => Some(repr),
+            Some(Err(err)) => return err.to_compile_error().into(),
+            None => None,
+        };
+        // Tuples may be stored as named sub-documents instead of arrays
+        let tuple_fields = match mongo_ops.option("tuple_fields") {
+            Some(lit) => {
+                let names: Vec<String> = lit
+                    .value()
+                    .split(',')
+                    .map(|name| name.trim().to_string())
+                    .collect();
+                match strip_type_groups(field_type) {
+                    Type::Tuple(tuple) if tuple.elems.len() == names.len() => Some(names),
+                    _ => {
+                        return syn::Error::new(
+                            lit.span(),
+                            "`tuple_fields` requires a tuple field with one name per element",
+                        )
+                        .to_compile_error()
+                        .into();
+                    }
+                }
+            }
+            None => None,
+        };
+        let owned_type = match mongo_ops.option("owned").map(LitStr::parse::<Type>) {
+            Some(Ok(owned_type)) => Some(owned_type),
+            Some(Err(err)) => return err.to_compile_error().into(),
+            None => None,
+        };
+        // Values may be normalized by a `fn(&T) -> T` before being staged
+        let transform = match mongo_ops
+            .option("transform")
+            .map(LitStr::parse::<syn::Path>)
+        {
+            Some(Ok(transform)) => Some(transform),
+            Some(Err(err)) => return err.to_compile_error().into(),
+            None => None,
+        };
+        let setter = match setter_value(
+            field_type,
+            discriminant.as_ref(),
+            owned_type.as_ref(),
+            transform.as_ref(),
+            tuple_fields.as_deref(),
+        ) {
+            Ok(setter) => setter,
+            Err(err) => return err.to_compile_error().into(),
+        };
+        let SetterValue {
+            generics: setter_generics,
+            where_clause: setter_where_clause,
+            param: setter_param,
+            prepare: setter_prepare,
+            storage: setter_storage,
+            stage: setter_stage,
+            convert: setter_convert,
+            nullable,
+        } = &setter;
+
+        // Type aliases hide the Vec, so the element type may be given explicitly
+        let vec_inner = match mongo_ops.option("vec_inner").map(LitStr::parse::<Type>) {
+            Some(Ok(inner_type)) => Some(inner_type),
+            Some(Err(err)) => return err.to_compile_error().into(),
+            None => None,
+        };
+        let inner_type = vec_inner
+            .as_ref()
+            .or_else(|| get_vec_inner_type(field_type));
 
         // Handle push operations for Vec types
         if ops.contains(&"push".to_string()) {
-            if let Some(inner_type) = get_vec_inner_type(field_type) {
+            if let Some(inner_type) = inner_type {
+                let serializer = element_serializer(inner_type);
+                has_push = true;
                 let field_storage = format_ident!("push_{}", field_name);
                 builder_fields.push(quote! {
-                    #field_storage: Option<#inner_type>
+                    #field_storage: Option<#push_options_name<#inner_type>>
+                });
+                merge_stagings.push(quote! {
+                    if let Some(options) = other.#field_storage {
+                        match &mut self.#field_storage {
+                            Some(staged) => {
+                                staged.values.extend(options.values);
+                                staged.position = options.position.or(staged.position);
+                                staged.slice = options.slice.or(staged.slice);
+                                staged.sort = options.sort.or(staged.sort.take());
+                            }
+                            None => self.#field_storage = Some(options),
+                        }
+                    }
                 });
 
                 let method_name = format_ident!("push_{}", field_name);
+                described.push((method_name.to_string(), "$push"));
                 builder_methods.push(quote! {
+                    /// Appends a value to the values pushed with `$each`.
                     pub fn #method_name(mut self, value: #inner_type) -> Self {
-                        self.#field_storage = Some(value);
+                        self.record_with(stringify!(#method_name), "$push", #field_key, || #serializer(&value).ok());
+                        self.#field_storage
+                            .get_or_insert_with(Default::default)
+                            .values
+                            .push(value);
                         self
                     }
                 });
 
-                push_conversions.push(quote! {
-                    if let Some(value) = &self.#field_storage {
-                        push_doc.insert(#field_name_str, doc! {
-                            "$each": [bson::to_bson(value)?]
+                let many_method_name = format_ident!("push_many_{}", field_name);
+                described.push((many_method_name.to_string(), "$push"));
+                builder_methods.push(quote! {
+                    /// Appends several values to the values pushed with `$each`.
+                    pub fn #many_method_name(mut self, values: impl IntoIterator<Item = #inner_type>) -> Self {
+                        let values: Vec<#inner_type> = values.into_iter().collect();
+                        self.record_with(stringify!(#many_method_name), "$push", #field_key, || {
+                            values.iter().map(#serializer).collect::<Result<Vec<_>, _>>().ok().map(bson::Bson::Array)
                         });
+                        self.#field_storage
+                            .get_or_insert_with(Default::default)
+                            .values
+                            .extend(values);
+                        self
                     }
                 });
-            }
-        }
-
-        // Handle pull operations for Vec types
-        if ops.contains(&"pull".to_string()) {
-            if let Some(inner_type) = get_vec_inner_type(field_type) {
-                let field_storage = format_ident!("pull_{}", field_name);
-                builder_fields.push(quote! {
-                    #field_storage: Option<#inner_type>
-                });
 
-                let method_name = format_ident!("pull_{}", field_name);
+                let with_method_name = format_ident!("push_{}_with", field_name);
+                described.push((with_method_name.to_string(), "$push"));
                 builder_methods.push(quote! {
-                    pub fn #method_name(mut self, value: #inner_type) -> Self {
-                        self.#field_storage = Some(value);
+                    /// Pushes values with the `$position`, `$slice` and `$sort` modifiers
+                    /// that are set in `options`.
+                    pub fn #with_method_name(mut self, options: #push_options_name<#inner_type>) -> Self {
+                        self.record_with(stringify!(#with_method_name), "$push", #field_key, || {
+                            options.values.iter().map(#serializer).collect::<Result<Vec<_>, _>>().ok().map(bson::Bson::Array)
+                        });
+                        self.#field_storage = Some(options);
                         self
                     }
                 });
 
-                pull_conversions.push(quote! {
-                    if let Some(value) = &self.#field_storage {
-                        pull_doc.insert(#field_name_str, doc! {
-                            "$in": [bson::to_bson(value)?]
-                        });
+                let sort_method_name = format_ident!("sort_{}", field_name);
+                described.push((sort_method_name.to_string(), "$push"));
+                builder_methods.push(quote! {
+                    /// Re-sorts the array in place by pushing no values with a `$sort`
+                    /// modifier, either `1`/`-1` or a document of sort fields.
+                    ///
+                    /// Any values and modifiers already staged for the field are kept.
+                    pub fn #sort_method_name(mut self, sort: impl Into<bson::Bson>) -> Self {
+                        let sort = sort.into();
+                        self.record(stringify!(#sort_method_name), "$push", #field_key, &sort);
+                        self.#field_storage.get_or_insert_with(Default::default).sort = Some(sort);
+                        self
                     }
                 });
+
+                let each_values = elements_bson(quote! { options.values }, inner_type);
+                push_conversions.push(fallible_conversion(
+                    &field_key,
+                    quote! {
+                        if let Some(options) = &self.#field_storage {
+                            let mut modifiers = Document::new();
+                            modifiers.insert("$each", #each_values);
+                            if let Some(position) = options.position {
+                                modifiers.insert("$position", position);
+                            }
+                            if let Some(slice) = options.slice {
+                                modifiers.insert("$slice", slice);
+                            }
+                            if let Some(sort) = &options.sort {
+                                modifiers.insert("$sort", sort.clone());
+                            }
+                            push_doc.insert(#field_key, modifiers);
+                        }
+                    },
+                ));
+            } else {
+                return syn::Error::new(
+                    field_type.span(),
+                    "`push` requires a `Vec` field; use `vec_inner` for type aliases",
+                )
+                .to_compile_error()
+                .into();
             }
         }
 
-        // Handle set operations
-        if ops.contains(&"set".to_string()) || ops.is_empty() {
-            // Generate set methods for all types, including Vec
-            let field_storage = format_ident!("set_{}", field_name);
+        // Handle addToSet operations for Vec types
+        if ops.contains(&"add_to_set".to_string()) {
+            if let Some(inner_type) = inner_type {
+                let serializer = element_serializer(inner_type);
+                let field_storage = format_ident!("add_to_set_{}", field_name);
+                builder_fields.push(quote! {
+                    #field_storage: Vec<#inner_type>
+                });
+                merge_stagings.push(quote! {
+                    self.#field_storage.extend(other.#field_storage);
+                });
+
+                let method_name = format_ident!("add_to_set_{}", field_name);
+                described.push((method_name.to_string(), "$addToSet"));
+                builder_methods.push(quote! {
+                    /// Adds a value to the array unless it is already present. Values
+                    /// added through repeated calls are sent together with `$each`.
+                    pub fn #method_name(mut self, value: #inner_type) -> Self {
+                        self.record_with(stringify!(#method_name), "$addToSet", #field_key, || #serializer(&value).ok());
+                        self.#field_storage.push(value);
+                        self
+                    }
+                });
+
+                let each_values = elements_bson(quote! { self.#field_storage }, inner_type);
+                add_to_set_conversions.push(fallible_conversion(
+                    &field_key,
+                    quote! {
+                        if !self.#field_storage.is_empty() {
+                            let mut modifiers = Document::new();
+                            modifiers.insert("$each", #each_values);
+                            add_to_set_doc.insert(#field_key, modifiers);
+                        }
+                    },
+                ));
+            } else {
+                return syn::Error::new(
+                    field_type.span(),
+                    "`add_to_set` requires a `Vec` field; use `vec_inner` for type aliases",
+                )
+                .to_compile_error()
+                .into();
+            }
+        }
+
+        // Handle pull operations for Vec types
+        if ops.contains(&"pull".to_string()) {
+            if let Some(inner_type) = inner_type {
+                let serializer = element_serializer(inner_type);
+                let field_storage = format_ident!("pull_{}", field_name);
+                builder_fields.push(quote! {
+                    #field_storage: Vec<#inner_type>
+                });
+                merge_stagings.push(quote! {
+                    self.#field_storage.extend(other.#field_storage);
+                });
+
+                let lt_storage = format_ident!("pull_{}_lt", field_name);
+                let gt_storage = format_ident!("pull_{}_gt", field_name);
+                builder_fields.push(quote! {
+                    #lt_storage: Option<#inner_type>
+                });
+                builder_fields.push(quote! {
+                    #gt_storage: Option<#inner_type>
+                });
+                let where_storage = format_ident!("pull_{}_where", field_name);
+                builder_fields.push(quote! {
+                    #where_storage: Option<bson::Document>
+                });
+                let all_storage = format_ident!("pull_all_{}", field_name);
+                builder_fields.push(quote! {
+                    #all_storage: Vec<#inner_type>
+                });
+                merge_stagings.push(quote! {
+                    self.#all_storage.extend(other.#all_storage);
+                });
+                for storage in [&lt_storage, &gt_storage, &where_storage] {
+                    merge_stagings.push(quote! {
+                        if other.#storage.is_some() {
+                            self.#storage = other.#storage;
+                        }
+                    });
+                }
+
+                let method_name = format_ident!("pull_{}", field_name);
+                let lt_method_name = format_ident!("pull_{}_lt", field_name);
+                let gt_method_name = format_ident!("pull_{}_gt", field_name);
+                let where_method_name = format_ident!("pull_{}_where", field_name);
+                let all_method_name = format_ident!("pull_all_{}", field_name);
+                described.push((method_name.to_string(), "$pull"));
+                described.push((lt_method_name.to_string(), "$pull"));
+                described.push((gt_method_name.to_string(), "$pull"));
+                described.push((where_method_name.to_string(), "$pull"));
+                described.push((all_method_name.to_string(), "$pullAll"));
+                builder_methods.push(quote! {
+                    /// Pulls every element equal to `value`. Values pulled through
+                    /// repeated calls are matched together with `$in`, which cannot be
+                    /// combined with the `$lt`/`$gt` range conditions on the field.
+                    pub fn #method_name(mut self, value: #inner_type) -> Self {
+                        self.record_with(stringify!(#method_name), "$pull", #field_key, || #serializer(&value).ok());
+                        self.#field_storage.push(value);
+                        self
+                    }
+
+                    /// Pulls every element matching `condition`, e.g. the fields of a
+                    /// sub-document or query operators such as `{ "$gte": 6 }`. It cannot
+                    /// be combined with the other `$pull` conditions on the field, which
+                    /// fails the build; `pull_all_<field>` may still be staged alongside.
+                    pub fn #where_method_name(mut self, condition: bson::Document) -> Self {
+                        self.record(stringify!(#where_method_name), "$pull", #field_key, &condition);
+                        self.#where_storage = Some(condition);
+                        self
+                    }
+
+                    /// Pulls every element equal to one of `values` with `$pullAll`.
+                    pub fn #all_method_name(mut self, values: impl IntoIterator<Item = #inner_type>) -> Self {
+                        let values: Vec<#inner_type> = values.into_iter().collect();
+                        self.record_with(stringify!(#all_method_name), "$pullAll", #field_key, || {
+                            values.iter().map(#serializer).collect::<Result<Vec<_>, _>>().ok().map(bson::Bson::Array)
+                        });
+                        self.#all_storage.extend(values);
+                        self
+                    }
+
+                    /// Pulls every element less than `value` (`$lt`). Combined with a
+                    /// `$gt` bound, only the elements within the range are removed.
+                    pub fn #lt_method_name(mut self, value: #inner_type) -> Self {
+                        self.record_with(stringify!(#lt_method_name), "$pull", #field_key, || #serializer(&value).ok());
+                        self.#lt_storage = Some(value);
+                        self
+                    }
+
+                    /// Pulls every element greater than `value` (`$gt`). Combined with a
+                    /// `$lt` bound, only the elements within the range are removed.
+                    pub fn #gt_method_name(mut self, value: #inner_type) -> Self {
+                        self.record_with(stringify!(#gt_method_name), "$pull", #field_key, || #serializer(&value).ok());
+                        self.#gt_storage = Some(value);
+                        self
+                    }
+                });
+
+                let in_values = elements_bson(quote! { self.#field_storage }, inner_type);
+                let all_values = elements_bson(quote! { self.#all_storage }, inner_type);
+                pull_conversions.push(fallible_conversion(
+                    &field_key,
+                    quote! {
+                        let mut condition = Document::new();
+                        if !self.#field_storage.is_empty() {
+                            // Sharing a condition would only match values that are both
+                            // listed and within the range, narrowing both pulls
+                            if self.#lt_storage.is_some() || self.#gt_storage.is_some() {
+                                return Err(mongodb::error::Error::custom(format!(
+                                    "`{}` cannot be combined with range pull conditions on `{}`",
+                                    stringify!(#method_name),
+                                    #field_key
+                                )));
+                            }
+                            condition.insert("$in", #in_values);
+                        }
+                        if let Some(value) = &self.#lt_storage {
+                            condition.insert("$lt", #serializer(value)?);
+                        }
+                        if let Some(value) = &self.#gt_storage {
+                            condition.insert("$gt", #serializer(value)?);
+                        }
+                        if let Some(where_condition) = &self.#where_storage {
+                            // A sub-document condition can't share a document with
+                            // `$in`, `$lt` and `$gt`, which only apply to scalar elements
+                            if !condition.is_empty() {
+                                return Err(mongodb::error::Error::custom(format!(
+                                    "`{}` cannot be combined with other pull conditions on `{}`",
+                                    stringify!(#where_method_name),
+                                    #field_key
+                                )));
+                            }
+                            condition = where_condition.clone();
+                        }
+                        if !condition.is_empty() {
+                            pull_doc.insert(#field_key, condition);
+                        }
+                        if !self.#all_storage.is_empty() {
+                            pull_all_doc.insert(#field_key, #all_values);
+                        }
+                    },
+                ));
+            } else {
+                return syn::Error::new(
+                    field_type.span(),
+                    "`pull` requires a `Vec` field; use `vec_inner` for type aliases",
+                )
+                .to_compile_error()
+                .into();
+            }
+        }
+
+        // Handle increment operations
+        if ops.contains(&"inc".to_string()) {
+            numeric_assertions.push(quote_spanned! { field_type.span() =>
+                assert_numeric::<#field_type>();
+            });
+
+            let field_storage = format_ident!("inc_{}", field_name);
             builder_fields.push(quote! {
                 #field_storage: Option<#field_type>
             });
+            merge_stagings.push(quote! {
+                if other.#field_storage.is_some() {
+                    self.#field_storage = other.#field_storage;
+                }
+            });
 
-            let method_name = format_ident!("set_{}", field_name);
+            let method_name = format_ident!("inc_{}", field_name);
+            described.push((method_name.to_string(), "$inc"));
             builder_methods.push(quote! {
                 pub fn #method_name(mut self, value: #field_type) -> Self {
+                    self.record(stringify!(#method_name), "$inc", #field_key, &value);
                     self.#field_storage = Some(value);
                     self
                 }
             });
 
-            set_conversions.push(quote! {
-                if let Some(value) = &self.#field_storage {
-                    set_doc.insert(#field_name_str, bson::to_bson(value)?);
+            // Integer counters can be guarded against overflow client-side
+            if is_integer_type(field_type) {
+                let checked_method_name = format_ident!("inc_{}_checked", field_name);
+                described.push((checked_method_name.to_string(), "$inc"));
+                builder_methods.push(quote! {
+                    /// Increments the field after checking that adding `value` to the
+                    /// caller-supplied `current` value cannot overflow.
+                    ///
+                    /// # Returns
+                    ///
+                    /// Result containing the builder instance or an error if the
+                    /// increment would overflow
+                    pub fn #checked_method_name(
+                        mut self,
+                        current: #field_type,
+                        value: #field_type
+                    ) -> Result<Self, mongodb::error::Error> {
+                        if current.checked_add(value).is_none() {
+                            return Err(mongodb::error::Error::custom(format!(
+                                "incrementing `{}` from {} by {} would overflow",
+                                #field_key, current, value
+                            )));
+                        }
+                        self.record(stringify!(#checked_method_name), "$inc", #field_key, &value);
+                        self.#field_storage = Some(value);
+                        Ok(self)
+                    }
+                });
+            }
+
+            inc_conversions.push(fallible_conversion(
+                &field_key,
+                quote! {
+                    if let Some(value) = &self.#field_storage {
+                        inc_doc.insert(#field_key, bson::to_bson(value)?);
+                    }
+                },
+            ));
+        }
+
+        // Handle multiply operations
+        if ops.contains(&"mul".to_string()) {
+            numeric_assertions.push(quote_spanned! { field_type.span() =>
+                assert_numeric::<#field_type>();
+            });
+
+            let field_storage = format_ident!("mul_{}", field_name);
+            builder_fields.push(quote! {
+                #field_storage: Option<#field_type>
+            });
+            merge_stagings.push(quote! {
+                if other.#field_storage.is_some() {
+                    self.#field_storage = other.#field_storage;
+                }
+            });
+
+            let method_name = format_ident!("mul_{}", field_name);
+            described.push((method_name.to_string(), "$mul"));
+            builder_methods.push(quote! {
+                /// Multiplies the field by `factor`.
+                pub fn #method_name(mut self, factor: #field_type) -> Self {
+                    self.record(stringify!(#method_name), "$mul", #field_key, &factor);
+                    self.#field_storage = Some(factor);
+                    self
+                }
+            });
+
+            mul_conversions.push(fallible_conversion(
+                &field_key,
+                quote! {
+                    if let Some(factor) = &self.#field_storage {
+                        mul_doc.insert(#field_key, bson::to_bson(factor)?);
+                    }
+                },
+            ));
+        }
+
+        // Handle bitwise operations; the last one staged for the field wins
+        if ops.contains(&"bit".to_string()) {
+            bitwise_assertions.push(quote_spanned! { field_type.span() =>
+                assert_bitwise::<#field_type>();
+            });
+
+            let field_storage = format_ident!("bit_{}", field_name);
+            builder_fields.push(quote! {
+                #field_storage: Option<(&'static str, #field_type)>
+            });
+            merge_stagings.push(quote! {
+                if other.#field_storage.is_some() {
+                    self.#field_storage = other.#field_storage;
+                }
+            });
+
+            for (op, doc) in [("and", "AND"), ("or", "OR"), ("xor", "XOR")] {
+                let method_name = format_ident!("bit_{}_{}", op, field_name);
+                let doc = format!(
+                    "Combines the field with `mask` by bitwise {}, replacing any other \
+                     bitwise operation staged for the field.",
+                    doc
+                );
+                described.push((method_name.to_string(), "$bit"));
+                builder_methods.push(quote! {
+                    #[doc = #doc]
+                    pub fn #method_name(mut self, mask: #field_type) -> Self {
+                        self.record(stringify!(#method_name), "$bit", #field_key, &mask);
+                        self.#field_storage = Some((#op, mask));
+                        self
+                    }
+                });
+            }
+
+            bit_conversions.push(fallible_conversion(
+                &field_key,
+                quote! {
+                    if let Some((op, mask)) = &self.#field_storage {
+                        let mut operation = Document::new();
+                        operation.insert(*op, bson::to_bson(mask)?);
+                        bit_doc.insert(#field_key, operation);
+                    }
+                },
+            ));
+        }
+
+        // Handle min and max operations, applied only when the value is lower or higher
+        for (op, operator, doc) in [("min", "$min", "min_doc"), ("max", "$max", "max_doc")] {
+            if !ops.contains(&op.to_string()) {
+                continue;
+            }
+            // Discriminants are compared as the integers `set` stores them as
+            let (storage_type, staged) = match &discriminant {
+                Some(repr) => (quote! { #repr }, quote! { value as #repr }),
+                None => (quote! { #field_type }, quote! { value }),
+            };
+            let field_storage = format_ident!("{}_{}", op, field_name);
+            builder_fields.push(quote! {
+                #field_storage: Option<#storage_type>
+            });
+            merge_stagings.push(quote! {
+                if other.#field_storage.is_some() {
+                    self.#field_storage = other.#field_storage;
+                }
+            });
+
+            let method_name = format_ident!("{}_{}", op, field_name);
+            described.push((method_name.to_string(), operator));
+            builder_methods.push(quote! {
+                pub fn #method_name(mut self, value: #field_type) -> Self {
+                    self.record(stringify!(#method_name), #operator, #field_key, &value);
+                    self.#field_storage = Some(#staged);
+                    self
+                }
+            });
+
+            // `SystemTime` is compared as a BSON date, as it is stored by `set`
+            let converted = if is_system_time_type(field_type) {
+                system_time_bson(quote! { *value }, field_type)
+            } else {
+                quote! { bson::to_bson(value)? }
+            };
+            let doc = format_ident!("{}", doc);
+            bound_conversions.push(fallible_conversion(
+                &field_key,
+                quote! {
+                    if let Some(value) = &self.#field_storage {
+                        #doc.insert(#field_key, #converted);
+                    }
+                },
+            ));
+        }
+
+        // Handle set-on-insert operations, applied only when an upsert inserts
+        if ops.contains(&"set_on_insert".to_string()) {
+            let field_storage = format_ident!("set_on_insert_{}", field_name);
+            builder_fields.push(quote! {
+                #field_storage: Option<#setter_storage>
+            });
+            merge_stagings.push(quote! {
+                if other.#field_storage.is_some() {
+                    self.#field_storage = other.#field_storage;
+                }
+            });
+
+            // Values are taken and stored the same way as by `set_<field>`
+            let method_name = format_ident!("set_on_insert_{}", field_name);
+            described.push((method_name.to_string(), "$setOnInsert"));
+            builder_methods.push(quote! {
+                /// Sets the field only when an upsert inserts a new document.
+                pub fn #method_name #setter_generics(mut self, value: #setter_param) -> Self
+                #setter_where_clause
+                {
+                    #setter_prepare
+                    self.record(stringify!(#method_name), "$setOnInsert", #field_key, &value);
+                    self.#field_storage = Some(#setter_stage);
+                    self
+                }
+            });
+
+            insert_conversions.push(fallible_conversion(
+                &field_key,
+                quote! {
+                    if let Some(value) = &self.#field_storage {
+                        set_on_insert_doc.insert(#field_key, #setter_convert);
+                    }
+                },
+            ));
+        }
+
+        // Handle current date operations, stamped with the server's time
+        if ops.contains(&"current_date".to_string()) {
+            let field_storage = format_ident!("current_date_{}", field_name);
+            builder_fields.push(quote! {
+                #field_storage: Option<bson::Bson>
+            });
+            merge_stagings.push(quote! {
+                if other.#field_storage.is_some() {
+                    self.#field_storage = other.#field_storage;
+                }
+            });
+
+            let now_method_name = format_ident!("set_{}_now", field_name);
+            let timestamp_method_name = format_ident!("set_{}_timestamp", field_name);
+            described.push((now_method_name.to_string(), "$currentDate"));
+            described.push((timestamp_method_name.to_string(), "$currentDate"));
+            builder_methods.push(quote! {
+                /// Sets the field to the server's current date.
+                pub fn #now_method_name(mut self) -> Self {
+                    let spec = bson::Bson::Boolean(true);
+                    self.record(stringify!(#now_method_name), "$currentDate", #field_key, &spec);
+                    self.#field_storage = Some(spec);
+                    self
+                }
+
+                /// Sets the field to the server's current time as a BSON timestamp.
+                pub fn #timestamp_method_name(mut self) -> Self {
+                    let mut spec = bson::Document::new();
+                    spec.insert("$type", "timestamp");
+                    let spec = bson::Bson::Document(spec);
+                    self.record(stringify!(#timestamp_method_name), "$currentDate", #field_key, &spec);
+                    self.#field_storage = Some(spec);
+                    self
+                }
+            });
+
+            current_date_conversions.push(quote! {
+                if let Some(spec) = &self.#field_storage {
+                    current_date_doc.insert(#field_key, spec.clone());
+                }
+            });
+        }
+
+        // Handle unset operations
+        if ops.contains(&"unset".to_string()) {
+            let field_storage = format_ident!("unset_{}", field_name);
+            builder_fields.push(quote! {
+                #field_storage: bool
+            });
+
+            // `$set` and `$unset` on the same path conflict, so the later one wins
+            let set_storage = format_ident!("set_{}", field_name);
+            let (conflict_check, clear_set) = if ops.contains(&"set".to_string()) {
+                (
+                    conflict_check_for(&set_storage, &field_key),
+                    quote! { self.#set_storage = None; },
+                )
+            } else {
+                (path_key_conflict_check(&field_key), quote! {})
+            };
+            merge_stagings.push(quote! {
+                if other.#field_storage {
+                    #conflict_check
+                    self.#field_storage = true;
+                    #clear_set
+                    self.path_updates.remove(#field_key);
+                }
+            });
+
+            let method_name = format_ident!("unset_{}", field_name);
+            described.push((method_name.to_string(), "$unset"));
+            builder_methods.push(quote! {
+                /// Removes the field from the document (`$unset`), replacing any value
+                /// staged for it, and replaced in turn by a later setter call.
+                pub fn #method_name(mut self) -> Self {
+                    self.record(stringify!(#method_name), "$unset", #field_key, "");
+                    #conflict_check
+                    #clear_set
+                    self.path_updates.remove(#field_key);
+                    self.#field_storage = true;
+                    self
+                }
+            });
+
+            unset_conversions.push(quote! {
+                if self.#field_storage {
+                    unset_doc.insert(#field_key, "");
+                }
+            });
+        }
+
+        // Handle setters for the declared sub-paths of a wrapper type
+        if let Some(sub_paths) = mongo_ops.option("sub_paths") {
+            let Some(value_type) = get_first_type_argument(field_type) else {
+                return syn::Error::new_spanned(
+                    field_type,
+                    "`sub_paths` requires a generic wrapper type such as `Localized<T>`",
+                )
+                .to_compile_error()
+                .into();
+            };
+
+            for sub_path in sub_paths.value().split(',').map(str::trim) {
+                let sub_path_ident = match syn::parse_str::<Ident>(sub_path) {
+                    Ok(ident) => ident,
+                    Err(_) => {
+                        return syn::Error::new_spanned(
+                            sub_paths,
+                            format!("`{}` is not a valid sub-path name", sub_path),
+                        )
+                        .to_compile_error()
+                        .into();
+                    }
+                };
+                let field_storage = format_ident!("set_{}_{}", field_name, sub_path_ident);
+                builder_fields.push(quote! {
+                    #field_storage: Option<#value_type>
+                });
+
+                let method_name = format_ident!("set_{}_{}", field_name, sub_path_ident);
+                let path = format!("{}.{}", field_key, sub_path);
+                described.push((method_name.to_string(), "$set"));
+                let conflict_check = conflict_check_for(&field_storage, &path);
+                merge_stagings.push(quote! {
+                    if other.#field_storage.is_some() {
+                        #conflict_check
+                        self.#field_storage = other.#field_storage;
+                    }
+                });
+                builder_methods.push(quote! {
+                    pub fn #method_name(mut self, value: #value_type) -> Self {
+                        self.record(stringify!(#method_name), "$set", #path, &value);
+                        #conflict_check
+                        self.#field_storage = Some(value);
+                        self
+                    }
+                });
+
+                set_conversions.push(fallible_conversion(
+                    &path,
+                    quote! {
+                        if let Some(value) = &self.#field_storage {
+                            set_doc.insert(#path, bson::to_bson(value)?);
+                        }
+                    },
+                ));
+            }
+        }
+
+        // Handle set operations
+        if ops.contains(&"set".to_string()) || ops.is_empty() {
+            // Generate set methods for all types, including Vec
+            let field_storage = format_ident!("set_{}", field_name);
+            let mut conflict_check = conflict_check_for(&field_storage, &field_key);
+            // Staging a value replaces an `$unset` of the field
+            let clear_unset = if ops.contains(&"unset".to_string()) {
+                let unset_storage = format_ident!("unset_{}", field_name);
+                if on_conflict_error {
+                    conflict_check = quote! {
+                        if self.#field_storage.is_some()
+                            || self.#unset_storage
+                            || self.path_updates.contains_key(#field_key)
+                        {
+                            self.conflicts.push(#field_key.to_string());
+                        }
+                    };
+                }
+                quote! { self.#unset_storage = false; }
+            } else {
+                quote! {}
+            };
+            merge_stagings.push(quote! {
+                if other.#field_storage.is_some() {
+                    #conflict_check
+                    self.#field_storage = other.#field_storage;
+                    #clear_unset
+                    self.path_updates.remove(#field_key);
+                }
+            });
+            let method_name = format_ident!("set_{}", field_name);
+            described.push((method_name.to_string(), "$set"));
+
+            diff_stagings.push(diff_staging(
+                field_name,
+                &field_key,
+                field_type,
+                diff_none_as_null,
+                tuple_fields.as_deref(),
+                discriminant.as_ref(),
+            ));
+
+            builder_fields.push(quote! {
+                #field_storage: Option<#setter_storage>
+            });
+            builder_methods.push(quote! {
+                pub fn #method_name #setter_generics(mut self, value: #setter_param) -> Self
+                #setter_where_clause
+                {
+                    #setter_prepare
+                    self.record(stringify!(#method_name), "$set", #field_key, &value);
+                    #conflict_check
+                    #clear_unset
+                    self.path_updates.remove(#field_key);
+                    self.#field_storage = Some(#setter_stage);
+                    self
                 }
             });
+
+            // `on_none` decides what staging `None` in an `Option` field does, and
+            // so which operator the null methods record
+            let (stage_none, null_operator) = match mongo_ops.option("on_none") {
+                Some(lit) if !*nullable => {
+                    return syn::Error::new(lit.span(), "`on_none` requires an `Option` field")
+                        .to_compile_error()
+                        .into();
+                }
+                Some(lit) if lit.value() == "null" => (None, Some("$set")),
+                Some(lit) if lit.value() == "unset" => (
+                    Some(quote! {
+                        unset_doc.insert(#field_key, "");
+                    }),
+                    Some("$unset"),
+                ),
+                Some(lit) if lit.value() == "skip" => (Some(quote! {}), None),
+                Some(lit) => {
+                    return syn::Error::new(
+                        lit.span(),
+                        "expected `on_none = \"null\"`, `\"unset\"` or `\"skip\"`",
+                    )
+                    .to_compile_error()
+                    .into();
+                }
+                None => (None, Some("$set")),
+            };
+
+            if *nullable {
+                // `Option<Box<T>>` fields are cleared, `Option<T>` fields set to null
+                let boxed = get_option_inner_type(field_type)
+                    .and_then(get_box_inner_type)
+                    .is_some();
+                let (null_method_name, doc) = if boxed {
+                    (
+                        format_ident!("clear_{}", field_name),
+                        "Clears the field, set to null unless `on_none` says otherwise.",
+                    )
+                } else {
+                    (
+                        format_ident!("set_{}_null", field_name),
+                        "Stages `None` for the field, set to null unless `on_none` says otherwise.",
+                    )
+                };
+                // Nothing is recorded when `None` is skipped, as nothing is staged
+                let record = match null_operator {
+                    Some("$unset") => quote! {
+                        self.record(stringify!(#null_method_name), "$unset", #field_key, "");
+                    },
+                    Some(operator) => quote! {
+                        self.record(stringify!(#null_method_name), #operator, #field_key, &bson::Bson::Null);
+                    },
+                    None => quote! {},
+                };
+                described.push((
+                    null_method_name.to_string(),
+                    null_operator.unwrap_or("$set"),
+                ));
+                builder_methods.push(quote! {
+                    #[doc = #doc]
+                    pub fn #null_method_name(mut self) -> Self {
+                        #record
+                        #conflict_check
+                        #clear_unset
+                        self.path_updates.remove(#field_key);
+                        self.#field_storage = Some(None);
+                        self
+                    }
+                });
+            }
+
+            // Values serialized once can be staged without serializing them again
+            let storage_type = owned_type.as_ref().unwrap_or(field_type);
+            let pre_serializable = discriminant.is_none()
+                && get_option_inner_type(field_type)
+                    .and_then(get_box_inner_type)
+                    .is_none()
+                && !is_system_time_type(storage_type)
+                && tuple_fields.is_none();
+            if pre_serializable {
+                let pre_method_name = format_ident!("set_{}_pre", field_name);
+                described.push((pre_method_name.to_string(), "$set"));
+                builder_methods.push(quote! {
+                    /// Sets the field to a value serialized once by `PreSerialized::new`,
+                    /// replacing any value given to the plain setter, and replaced in
+                    /// turn by a later call to it. The value is staged as is, without
+                    /// `transform`.
+                    pub fn #pre_method_name(mut self, value: &#pre_serialized_name<#storage_type>) -> Self {
+                        self.record(stringify!(#pre_method_name), "$set", #field_key, value.as_bson());
+                        #conflict_check
+                        #clear_unset
+                        self.#field_storage = None;
+                        self.path_updates.insert(#field_key.to_string(), value.bson.clone());
+                        self
+                    }
+                });
+            }
+
+            let conversion = match stage_none {
+                Some(stage_none) => quote! {
+                    match &self.#field_storage {
+                        Some(None) => {
+                            #stage_none
+                        }
+                        Some(value) => {
+                            set_doc.insert(#field_key, #setter_convert);
+                        }
+                        None => {}
+                    }
+                },
+                None => quote! {
+                    if let Some(value) = &self.#field_storage {
+                        set_doc.insert(#field_key, #setter_convert);
+                    }
+                },
+            };
+            set_conversions.push(fallible_conversion(&field_key, conversion));
         }
     }
 
@@ -306,74 +2209,1069 @@ pub fn derive_mongo_update_builder(input: TokenStream) -> TokenStream {
         path_updates: std::collections::HashMap<String, bson::Bson>
     });
 
-    // Add direct path updates to set document
-    set_conversions.push(quote! {
-        for (path, value) in &self.path_updates {
-            set_doc.insert(path, value.clone());
-        }
+    // Add field for updates applied only when an upsert inserts a document
+    builder_fields.push(quote! {
+        insert_updates: std::collections::HashMap<String, bson::Bson>
+    });
+
+    // Add field for the `arrayFilters` matching positional `$[identifier]` paths
+    builder_fields.push(quote! {
+        array_filters: Vec<bson::Document>
+    });
+
+    // Add field for operator keys overridden for MongoDB-compatible backends
+    builder_fields.push(quote! {
+        operator_keys: std::collections::HashMap<String, String>
+    });
+
+    // Add flag for folding dotted paths into staged sub-documents
+    builder_fields.push(quote! {
+        deep_merge: bool
+    });
+
+    // Add flag for dropping `$setOnInsert` paths also set, when building for an upsert
+    builder_fields.push(quote! {
+        for_upsert: bool
+    });
+
+    // Add field for other operators' paths forwarded from nested builders
+    builder_fields.push(quote! {
+        operator_updates: Vec<(String, String, bson::Bson)>
+    });
+
+    // Add field for user-defined operators run after the built-in ones
+    builder_fields.push(quote! {
+        custom_operators: Vec<std::sync::Arc<dyn Fn(&mut bson::Document) + Send + Sync>>
     });
 
-    // Generate the UpdateBuilder struct
-    let expanded = quote! {
-        /// The update builder for the struct, generated by the `MongoOperations` derive macro.
-        ///
-        /// This struct provides methods for creating MongoDB update operations based on the
-        /// struct's fields and their annotations.
-        #[derive(Default, Clone)]
-        pub struct #builder_name {
-            #(#builder_fields,)*
-        }
+    // Add field for the nested builders that failed to build, reported by `build`
+    builder_fields.push(quote! {
+        nested_failures: Vec<(String, mongodb::error::Error)>
+    });
+
+    // Add field for the paths staged more than once, reported by `build`
+    let conflicts_check = if on_conflict_error {
+        builder_fields.push(quote! {
+            conflicts: Vec<String>
+        });
+        quote! {
+            if let Some(path) = self.conflicts.first() {
+                let error = mongodb::error::Error::custom(format!(
+                    "`{}` is staged more than once",
+                    path
+                ));
+                return match failures {
+                    Some(failures) => {
+                        failures.push((path.clone(), error));
+                        Ok((bson::Document::new(), Vec::new()))
+                    }
+                    None => Err(error),
+                };
+            }
+        }
+    } else {
+        quote! {}
+    };
+
+    // Add field for the staged operations recorded in call order, when enabled
+    builder_fields.push(quote! {
+        history: Option<Vec<#staged_op_name>>
+    });
+
+    // Add field for paths removed with `$unset`
+    builder_fields.push(quote! {
+        unset_paths: Vec<String>
+    });
+
+    // Add field for raw stages appended to `build_pipeline`'s output
+    builder_fields.push(quote! {
+        stages: Vec<bson::Document>
+    });
+
+    // Add marker for the struct's generic parameters, which fields may not all use
+    builder_fields.push(quote! {
+        model: std::marker::PhantomData<fn() -> #name #ty_generics>
+    });
+    let mut builder_field_names = Vec::new();
+    for builder_field in &builder_fields {
+        match syn::parse::Parser::parse2(syn::Field::parse_named, builder_field.clone()) {
+            Ok(field) => builder_field_names.extend(field.ident),
+            Err(err) => return err.to_compile_error().into(),
+        }
+    }
+
+    // Add direct path updates to set document, which may collide with typed setters
+    let staged_path_check = if on_conflict_error {
+        quote! {
+            if set_doc.contains_key(path) {
+                let error = mongodb::error::Error::custom(format!(
+                    "`{}` is staged more than once",
+                    path
+                ));
+                return match failures.as_deref_mut() {
+                    Some(failures) => {
+                        failures.push((path.clone(), error));
+                        Ok((Document::new(), Vec::new()))
+                    }
+                    None => Err(error),
+                };
+            }
+        }
+    } else {
+        quote! {}
+    };
+    set_conversions.push(quote! {
+        for (path, value) in &self.path_updates {
+            #staged_path_check
+            set_doc.insert(path, value.clone());
+        }
+    });
+
+    let diff_body = if diff_stagings.is_empty() {
+        quote! {
+            let _ = (old, new);
+            Ok(Self::new())
+        }
+    } else {
+        quote! {
+            let mut builder = Self::new();
+            #(#diff_stagings)*
+            Ok(builder)
+        }
+    };
+
+    let apply_one = apply_body(name, "apply", quote! { update_one }, false);
+    let apply_many = apply_body(name, "apply_many", quote! { update_many }, false);
+    let apply_upsert = apply_body(name, "apply_upsert", quote! { update_one }, true);
+
+    let (described_methods, described_operators): (Vec<_>, Vec<_>) = described.into_iter().unzip();
+
+    // Generate the push modifiers type when any field supports push
+    let push_options = if has_push {
+        quote! {
+            /// Values and modifiers for a `$push`, generated by the `MongoOperations`
+            /// derive macro. Only the modifiers that are set are emitted.
+            #[derive(Debug, Clone)]
+            pub struct #push_options_name<T> {
+                /// The values to push, emitted as `$each`.
+                pub values: Vec<T>,
+                /// The array index at which to insert the values (`$position`).
+                pub position: Option<i32>,
+                /// The number of elements to keep after pushing (`$slice`);
+                /// negative values keep the last elements.
+                pub slice: Option<i32>,
+                /// The order applied after pushing (`$sort`), either `1`/`-1` or a
+                /// document of sort fields such as `{ "score": -1 }`.
+                pub sort: Option<bson::Bson>,
+            }
+
+            impl<T> Default for #push_options_name<T> {
+                fn default() -> Self {
+                    Self {
+                        values: Vec::new(),
+                        position: None,
+                        slice: None,
+                        sort: None,
+                    }
+                }
+            }
+        }
+    } else {
+        quote! {}
+    };
+
+    // Generate the UpdateBuilder struct
+    let expanded = quote! {
+        #push_options
+
+        /// A value serialized to BSON once, generated by the `MongoOperations`
+        /// derive macro, for staging the same value in many builders through
+        /// `set_<field>_pre` without serializing it again.
+        pub struct #pre_serialized_name<T> {
+            bson: bson::Bson,
+            marker: std::marker::PhantomData<fn() -> T>,
+        }
+
+        impl<T: serde::Serialize> #pre_serialized_name<T> {
+            /// Serializes `value` once.
+            ///
+            /// # Returns
+            ///
+            /// Result containing the serialized value or a MongoDB error
+            pub fn new(value: T) -> Result<Self, mongodb::error::Error> {
+                Ok(Self {
+                    bson: bson::to_bson(&value)?,
+                    marker: std::marker::PhantomData,
+                })
+            }
+        }
+
+        impl<T> #pre_serialized_name<T> {
+            /// Returns the serialized value.
+            pub fn as_bson(&self) -> &bson::Bson {
+                &self.bson
+            }
+        }
+
+        impl<T> Clone for #pre_serialized_name<T> {
+            fn clone(&self) -> Self {
+                Self {
+                    bson: self.bson.clone(),
+                    marker: std::marker::PhantomData,
+                }
+            }
+        }
+
+        impl<T> std::fmt::Debug for #pre_serialized_name<T> {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                f.debug_tuple(stringify!(#pre_serialized_name)).field(&self.bson).finish()
+            }
+        }
+
+        /// An operation staged on a builder recording its history, generated by
+        /// the `MongoOperations` derive macro.
+        #[derive(Debug, Clone, PartialEq)]
+        pub struct #staged_op_name {
+            /// The builder method that staged the operation.
+            pub method: &'static str,
+            /// The update operator, e.g. `$set`.
+            pub operator: String,
+            /// The document path the operation applies to.
+            pub path: String,
+            /// The value passed to the method, or `None` if it failed to serialize.
+            pub value: Option<bson::Bson>,
+        }
+
+        /// An update built once by `freeze`, generated by the `MongoOperations`
+        /// derive macro, for applying the same update many times.
+        #[derive(Debug, Clone)]
+        pub struct #frozen_update_name {
+            update: bson::Document,
+            array_filters: Vec<bson::Document>,
+        }
+
+        impl #frozen_update_name {
+            /// Returns a copy of the built update document.
+            pub fn document(&self) -> bson::Document {
+                self.update.clone()
+            }
+
+            /// Returns a copy of the `arrayFilters` matching the update.
+            pub fn array_filters(&self) -> Vec<bson::Document> {
+                self.array_filters.clone()
+            }
+        }
+
+        /// The update builder for the struct, generated by the `MongoOperations` derive macro.
+        ///
+        /// This struct provides methods for creating MongoDB update operations based on the
+        /// struct's fields and their annotations.
+        #[derive(Clone)]
+        #[allow(clippy::type_complexity, clippy::struct_excessive_bools)]
+        pub struct #builder_name #generics #where_clause {
+            #(#builder_fields,)*
+        }
+
+        impl #impl_generics Default for #builder_name #ty_generics #where_clause {
+            fn default() -> Self {
+                Self {
+                    #(#builder_field_names: Default::default(),)*
+                }
+            }
+        }
+
+        impl #impl_generics #name #ty_generics #serialize_where_clause {
+            /// Creates a new update builder for this struct.
+            pub fn update_builder() -> #builder_name #ty_generics {
+                #builder_name::new()
+            }
+        }
+
+        #[allow(clippy::too_many_arguments, clippy::type_complexity, clippy::too_many_lines)]
+        impl #impl_generics #builder_name #ty_generics #serialize_where_clause {
+            /// Name of the struct this builder updates, e.g. for labelling logs and metrics.
+            pub const MODEL_NAME: &'static str = stringify!(#name);
+
+            /// Key of the field identifying the struct as an array element, following
+            /// serde renames. Matched by the `update_<field>_by_id` nested methods.
+            pub const ID_KEY: &'static str = #id_key;
+
+            /// Lists the field methods generated for this builder along with the
+            /// update operator each of them stages, following field declaration order.
+            ///
+            /// Generic path methods and methods added by `mongo_nested_fields`
+            /// are not included.
+            pub fn describe() -> &'static [(&'static str, &'static str)] {
+                &[#((#described_methods, #described_operators)),*]
+            }
+
+            /// Compile-time check that incremented and multiplied fields are numeric.
+            #[allow(dead_code)]
+            fn assert_numeric_fields() {
+                #[diagnostic::on_unimplemented(
+                    message = "`inc` and `mul` require a numeric field, found `{Self}`",
+                    label = "not a numeric type"
+                )]
+                trait Numeric {}
+                impl Numeric for i8 {}
+                impl Numeric for i16 {}
+                impl Numeric for i32 {}
+                impl Numeric for i64 {}
+                impl Numeric for i128 {}
+                impl Numeric for isize {}
+                impl Numeric for u8 {}
+                impl Numeric for u16 {}
+                impl Numeric for u32 {}
+                impl Numeric for u64 {}
+                impl Numeric for u128 {}
+                impl Numeric for usize {}
+                impl Numeric for f32 {}
+                impl Numeric for f64 {}
+                fn assert_numeric<T: Numeric>() {}
+                #(#numeric_assertions)*
+            }
+
+            /// Compile-time check that fields updated bitwise are integers stored as
+            /// BSON `int` or `long`, the only types `$bit` accepts.
+            #[allow(dead_code)]
+            fn assert_bitwise_fields() {
+                #[diagnostic::on_unimplemented(
+                    message = "`bit` requires an integer field, found `{Self}`",
+                    label = "not a 32 or 64-bit integer type"
+                )]
+                trait Bitwise {}
+                impl Bitwise for i8 {}
+                impl Bitwise for i16 {}
+                impl Bitwise for i32 {}
+                impl Bitwise for i64 {}
+                impl Bitwise for u8 {}
+                impl Bitwise for u16 {}
+                impl Bitwise for u32 {}
+                fn assert_bitwise<T: Bitwise>() {}
+                #(#bitwise_assertions)*
+            }
+
+            /// Creates a new, empty update builder.
+            ///
+            /// Equivalent to `update_builder()` on the struct and to `Default::default()`.
+            pub fn new() -> Self {
+                Self::default()
+            }
+
+            /// Creates a builder staging the changes that turn `old` into `new`.
+            ///
+            /// Settable fields are compared by their BSON form and set when they
+            /// differ. An `Option` field going from `Some` to `None` is unset, or set
+            /// to null with `#[mongo_ops(diff_none = "null")]` on the struct. Fields
+            /// stored by `discriminant` are compared by discriminant, which requires
+            /// their enum to implement `Clone`.
+            ///
+            /// # Returns
+            ///
+            /// Result containing the builder or a MongoDB error if a value fails to serialize
+            pub fn diff(old: &#name #ty_generics, new: &#name #ty_generics) -> Result<Self, mongodb::error::Error> {
+                #diff_body
+            }
+
+            #(#builder_methods)*
+
+            /// Generic method for updating any field by path.
+            ///
+            /// This method allows you to set fields that might not be directly accessible
+            /// through the generated methods, such as nested fields or fields with special characters.
+            ///
+            /// # Arguments
+            ///
+            /// * `field_path` - The dot notation path to the field
+            /// * `value` - The value to set for the field
+            ///
+            /// # Returns
+            ///
+            /// Result containing the builder instance or a MongoDB error
+            pub fn set_field<Value: serde::Serialize>(
+                mut self,
+                field_path: &str,
+                value: Value
+            ) -> Result<Self, mongodb::error::Error> {
+                #path_conflict_check
+                let value = bson::to_bson(&value)?;
+                self.record("set_field", "$set", field_path, &value);
+                self.path_updates.insert(field_path.to_string(), value);
+                Ok(self)
+            }
+
+            /// Sets a field by path only when an upsert inserts a new document,
+            /// through the `$setOnInsert` operator.
+            ///
+            /// # Arguments
+            ///
+            /// * `field_path` - The dot notation path to the field
+            /// * `value` - The value to set for the field on insert
+            ///
+            /// # Returns
+            ///
+            /// Result containing the builder instance or a MongoDB error
+            pub fn set_on_insert_field<Value: serde::Serialize>(
+                mut self,
+                field_path: &str,
+                value: Value
+            ) -> Result<Self, mongodb::error::Error> {
+                let value = bson::to_bson(&value)?;
+                self.record("set_on_insert_field", "$setOnInsert", field_path, &value);
+                self.insert_updates.insert(field_path.to_string(), value);
+                Ok(self)
+            }
+
+            /// Sets several fields by path from already serialized values.
+            ///
+            /// # Arguments
+            ///
+            /// * `fields` - Pairs of dot notation paths and the values to set for them
+            ///
+            /// # Returns
+            ///
+            /// The builder instance
+            pub fn set_many(mut self, fields: &[(&str, bson::Bson)]) -> Self {
+                for (field_path, value) in fields {
+                    #many_conflict_check
+                    self.record("set_many", "$set", field_path, value);
+                    self.path_updates.insert(field_path.to_string(), value.clone());
+                }
+                self
+            }
+
+            /// Merges the keys of a pre-computed document into the top-level `$set`,
+            /// alongside the values staged by the typed setters.
+            ///
+            /// # Arguments
+            ///
+            /// * `root` - Root-level fields and their values
+            ///
+            /// # Returns
+            ///
+            /// Result containing the builder instance or an error if a key starts
+            /// with `$`, which would be read as an operator
+            pub fn set_root(mut self, root: bson::Document) -> Result<Self, mongodb::error::Error> {
+                if let Some(key) = root.keys().find(|key| key.starts_with('$')) {
+                    return Err(mongodb::error::Error::custom(format!(
+                        "`set_root` key `{}` must not start with `$`",
+                        key
+                    )));
+                }
+                for (field_path, value) in root {
+                    let field_path = field_path.as_str();
+                    #path_conflict_check
+                    self.record("set_root", "$set", field_path, &value);
+                    self.path_updates.insert(field_path.to_string(), value);
+                }
+                Ok(self)
+            }
+
+            /// Adds an `arrayFilters` entry for a manually staged `$[identifier]` path.
+            ///
+            /// # Arguments
+            ///
+            /// * `filter` - The filter document, e.g. `{ "elem.grade": { "$gte": 85 } }`
+            ///
+            /// # Returns
+            ///
+            /// The builder instance
+            pub fn array_filter(mut self, filter: bson::Document) -> Self {
+                self.array_filters.push(filter);
+                self
+            }
+
+            /// Sets a field of the array elements matching `filter`, staging it under
+            /// the filtered positional path `field_path.$[identifier].sub_field` and
+            /// recording `filter` for `build_with_options`.
+            ///
+            /// # Arguments
+            ///
+            /// * `field_path` - The dot notation path to the array
+            /// * `identifier` - The identifier naming the matched elements, e.g. `elem`
+            /// * `sub_field` - The field to set within each matched element
+            /// * `value` - The value to set
+            /// * `filter` - The filter on `identifier`, e.g. `{ "elem.id": 3 }`
+            ///
+            /// # Returns
+            ///
+            /// Result containing the builder instance or a MongoDB error if the value
+            /// fails to serialize or the filter does not only reference `identifier`
+            pub fn set_array_element<Value: serde::Serialize>(
+                mut self,
+                field_path: &str,
+                identifier: &str,
+                sub_field: &str,
+                value: Value,
+                filter: bson::Document
+            ) -> Result<Self, mongodb::error::Error> {
+                let prefix = format!("{}.", identifier);
+                if filter.is_empty()
+                    || filter.keys().any(|key| key != identifier && !key.starts_with(&prefix))
+                {
+                    return Err(mongodb::error::Error::custom(format!(
+                        "array filter must only reference the identifier `{}`",
+                        identifier
+                    )));
+                }
+
+                let path = format!("{}.$[{}].{}", field_path, identifier, sub_field);
+                let field_path = path.as_str();
+                #path_conflict_check
+                let value = bson::to_bson(&value)?;
+                self.record("set_array_element", "$set", field_path, &value);
+                self.path_updates.insert(path, value);
+                self.array_filters.push(filter);
+                Ok(self)
+            }
+
+            /// Overrides the key emitted for an update operator.
+            ///
+            /// Standard MongoDB operator keys are used by default; this is meant for
+            /// MongoDB-compatible backends expecting different operator naming.
+            ///
+            /// # Arguments
+            ///
+            /// * `operator` - The standard operator, e.g. `"$set"`
+            /// * `key` - The key to emit in its place
+            ///
+            /// # Returns
+            ///
+            /// The builder instance
+            pub fn operator_key(mut self, operator: &str, key: &str) -> Self {
+                self.operator_keys.insert(operator.to_string(), key.to_string());
+                self
+            }
+
+            /// Enables deep merging of `$set` paths when building.
+            ///
+            /// MongoDB rejects an update that sets both a whole sub-document
+            /// (`address`) and a path inside it (`address.geo.lat`). In deep merge
+            /// mode, every dotted path whose prefix is staged as a whole document is
+            /// folded into that document instead, creating intermediate
+            /// sub-documents as needed, so neither update clobbers the other. A
+            /// document staged at such a path is merged key by key into the one
+            /// already there.
+            /// Paths under a prefix staged as a non-document value are left as is.
+            ///
+            /// # Returns
+            ///
+            /// The builder instance
+            pub fn deep_merge(mut self) -> Self {
+                self.deep_merge = true;
+                self
+            }
+
+            /// Combines the operations staged in `other` into this builder, e.g. to
+            /// issue partial updates assembled separately as one update.
+            ///
+            /// Values pushed or added to a set by both builders are combined into a
+            /// single `$each`, with `other`'s values last. For any other path staged
+            /// by both, `other`'s value wins, unless `on_conflict = "error"` is set,
+            /// in which case building fails.
+            ///
+            /// # Arguments
+            ///
+            /// * `other` - The builder whose operations are combined into this one
+            ///
+            /// # Returns
+            ///
+            /// The builder instance
+            pub fn merge(mut self, other: Self) -> Self {
+                #merge_conflict_check
+                #(#merge_stagings)*
+                self.path_updates.extend(other.path_updates);
+                self.insert_updates.extend(other.insert_updates);
+                self.array_filters.extend(other.array_filters);
+                self.operator_keys.extend(other.operator_keys);
+                self.deep_merge |= other.deep_merge;
+                self.operator_updates.extend(other.operator_updates);
+                self.custom_operators.extend(other.custom_operators);
+                self.nested_failures.extend(other.nested_failures);
+                self.unset_paths.extend(other.unset_paths);
+                self.stages.extend(other.stages);
+                if let Some(history) = other.history {
+                    self.history.get_or_insert_with(Vec::new).extend(history);
+                }
+                self
+            }
+
+            /// Adds a custom operator that modifies the update document when building.
+            ///
+            /// Custom operators run in the order they were added, after every built-in
+            /// operator has been written to the document and before `require_together`
+            /// rules are checked and `operator_key` renames are applied, so they see
+            /// and should produce the standard `$`-prefixed operator keys.
+            ///
+            /// Any function or closure taking `&mut Document` is accepted, so the same
+            /// operator, e.g. a plain `fn`, can be reused across the builders of
+            /// every model.
+            ///
+            /// # Arguments
+            ///
+            /// * `operator` - The operator modifying the update document being built
+            ///
+            /// # Returns
+            ///
+            /// The builder instance
+            pub fn with_operator(
+                mut self,
+                operator: impl Fn(&mut bson::Document) + Send + Sync + 'static
+            ) -> Self {
+                self.custom_operators.push(std::sync::Arc::new(operator));
+                self
+            }
+
+            /// Folds dotted `$set` paths into the staged sub-documents they extend.
+            fn merge_dotted_paths(set_doc: bson::Document) -> bson::Document {
+                fn insert_path(doc: &mut bson::Document, path: &str, value: bson::Bson) {
+                    match path.split_once('.') {
+                        Some((head, tail)) => {
+                            if !matches!(doc.get(head), Some(bson::Bson::Document(_))) {
+                                doc.insert(head, bson::Document::new());
+                            }
+                            if let Some(bson::Bson::Document(child)) = doc.get_mut(head) {
+                                insert_path(child, tail, value);
+                            }
+                        }
+                        None => match (doc.get_mut(path), value) {
+                            (Some(bson::Bson::Document(existing)), bson::Bson::Document(incoming)) => {
+                                merge_documents(existing, incoming);
+                            }
+                            (_, value) => {
+                                doc.insert(path, value);
+                            }
+                        },
+                    }
+                }
+
+                // Merges `incoming` into `existing` key by key, recursing into the
+                // sub-documents both sides share
+                fn merge_documents(existing: &mut bson::Document, incoming: bson::Document) {
+                    for (key, value) in incoming {
+                        match (existing.get_mut(&key), value) {
+                            (Some(bson::Bson::Document(child)), bson::Bson::Document(value)) => {
+                                merge_documents(child, value);
+                            }
+                            (_, value) => {
+                                existing.insert(key, value);
+                            }
+                        }
+                    }
+                }
+
+                let roots: Vec<String> = set_doc
+                    .iter()
+                    .filter(|(_, value)| matches!(value, bson::Bson::Document(_)))
+                    .map(|(key, _)| key.clone())
+                    .collect();
+
+                let mut merged = bson::Document::new();
+                let mut folded = Vec::new();
+                for (key, value) in set_doc {
+                    let root = roots
+                        .iter()
+                        .filter(|root| {
+                            key.len() > root.len()
+                                && key.starts_with(root.as_str())
+                                && key.as_bytes()[root.len()] == b'.'
+                        })
+                        .min_by_key(|root| root.len());
+                    match root {
+                        Some(root) => {
+                            let rest = key[root.len() + 1..].to_string();
+                            folded.push((root.clone(), rest, value));
+                        }
+                        None => {
+                            merged.insert(key, value);
+                        }
+                    }
+                }
+
+                // Shallower paths first so deeper ones merge into them
+                folded.sort_by_key(|(_, rest, _)| rest.matches('.').count());
+                for (root, rest, value) in folded {
+                    if let Some(bson::Bson::Document(doc)) = merged.get_mut(&root) {
+                        insert_path(doc, &rest, value);
+                    }
+                }
+                merged
+            }
+
+            /// Starts recording every staged operation, in call order, for `history`.
+            ///
+            /// # Returns
+            ///
+            /// The builder instance
+            pub fn with_history(mut self) -> Self {
+                self.history.get_or_insert_with(Vec::new);
+                self
+            }
+
+            /// Returns the operations staged since `with_history` was called, in
+            /// call order. Unlike the built update, operations superseded by later
+            /// calls for the same path are kept, so the caller's requests can be
+            /// audited or replayed.
+            pub fn history(&self) -> &[#staged_op_name] {
+                self.history.as_deref().unwrap_or_default()
+            }
+
+            /// Appends an operation to the history when it is being recorded.
+            fn record<Value: serde::Serialize + ?Sized>(
+                &mut self,
+                method: &'static str,
+                operator: &str,
+                path: &str,
+                value: &Value
+            ) {
+                self.record_with(method, operator, path, || bson::to_bson(value).ok());
+            }
+
+            /// Appends an operation to the history when it is being recorded, its
+            /// value being serialized by `value` only then.
+            fn record_with(
+                &mut self,
+                method: &'static str,
+                operator: &str,
+                path: &str,
+                value: impl FnOnce() -> Option<bson::Bson>
+            ) {
+                if let Some(history) = &mut self.history {
+                    history.push(#staged_op_name {
+                        method,
+                        operator: operator.to_string(),
+                        path: path.to_string(),
+                        value: value(),
+                    });
+                }
+            }
+
+            /// Serializes an array element. Elements are only serialized through
+            /// here, called at the field's type by `push`, `pull` and `add_to_set`
+            /// methods, so a missing `Serialize` implementation is reported once.
+            #[allow(dead_code)]
+            fn element_to_bson<Element: serde::Serialize>(
+                value: &Element
+            ) -> Result<bson::Bson, bson::ser::Error> {
+                bson::to_bson(value)
+            }
+
+            /// Whether an array filter already names `identifier`.
+            #[allow(dead_code)]
+            fn has_array_filter_identifier(&self, identifier: &str) -> bool {
+                self.array_filters
+                    .iter()
+                    .flat_map(|filter| filter.keys())
+                    .any(|key| key.split('.').next() == Some(identifier))
+            }
+
+            /// Returns the first of `e`, `e1`, `e2`, ... neither named by an array
+            /// filter nor listed in `taken`.
+            #[allow(dead_code)]
+            fn array_filter_identifier(&self, taken: &[String]) -> String {
+                let mut identifier = "e".to_string();
+                let mut n = 0;
+                while self.has_array_filter_identifier(&identifier) || taken.contains(&identifier) {
+                    n += 1;
+                    identifier = format!("e{}", n);
+                }
+                identifier
+            }
+
+            /// Stages a `$set` of `field_path` for the direct path methods generated
+            /// by `mongo_nested_fields`, whose name is recorded as `method` in the history.
+            ///
+            /// # Returns
+            ///
+            /// Result containing nothing or an error if `on_conflict = "error"` is
+            /// set and the path is already staged
+            #[allow(dead_code)]
+            fn set_nested_path(
+                &mut self,
+                method: &'static str,
+                field_path: &str,
+                value: bson::Bson
+            ) -> Result<(), mongodb::error::Error> {
+                #path_conflict_check
+                self.record(method, "$set", field_path, &value);
+                self.path_updates.insert(field_path.to_string(), value);
+                Ok(())
+            }
+
+            /// Stages every path of a nested builder's update under `prefix`,
+            /// keeping its operator. Used by the `mongo_nested_fields` methods,
+            /// whose name is recorded as `method` in the history.
+            ///
+            /// The nested builder's `array_filters` are forwarded to this builder,
+            /// with any identifier this builder already uses renamed in both the
+            /// filters and the `$[identifier]` segments of the staged paths.
+            ///
+            /// # Returns
+            ///
+            /// Whether any path was staged
+            #[allow(dead_code)]
+            fn merge_nested(
+                &mut self,
+                method: &'static str,
+                prefix: &str,
+                update: bson::Document,
+                array_filters: Vec<bson::Document>
+            ) -> bool {
+                let mut renames: Vec<(String, String)> = Vec::new();
+                let nested_identifiers: Vec<String> = array_filters
+                    .iter()
+                    .flat_map(|filter| filter.keys())
+                    .filter_map(|key| key.split('.').next())
+                    .filter(|identifier| !identifier.starts_with('$'))
+                    .map(str::to_string)
+                    .collect();
+                for identifier in &nested_identifiers {
+                    if renames.iter().any(|(from, _)| from == identifier) {
+                        continue;
+                    }
+                    let renamed = if self.has_array_filter_identifier(identifier) {
+                        let mut taken = nested_identifiers.clone();
+                        taken.extend(renames.iter().map(|(_, to)| to.clone()));
+                        self.array_filter_identifier(&taken)
+                    } else {
+                        identifier.clone()
+                    };
+                    renames.push((identifier.clone(), renamed));
+                }
+                let rename = |identifier: &str| {
+                    renames
+                        .iter()
+                        .find(|(from, _)| from == identifier)
+                        .map_or_else(|| identifier.to_string(), |(_, to)| to.clone())
+                };
+
+                let mut staged = false;
+                for (operator, paths) in update {
+                    let bson::Bson::Document(paths) = paths else {
+                        continue;
+                    };
+                    for (key, value) in paths {
+                        let key = key
+                            .split('.')
+                            .map(|segment| {
+                                match segment.strip_prefix("$[").and_then(|s| s.strip_suffix(']')) {
+                                    Some(identifier) if !identifier.is_empty() => {
+                                        format!("$[{}]", rename(identifier))
+                                    }
+                                    _ => segment.to_string(),
+                                }
+                            })
+                            .collect::<Vec<_>>()
+                            .join(".");
+                        let path = format!("{}.{}", prefix, key);
+                        self.record(method, &operator, &path, &value);
+                        match operator.as_str() {
+                            "$set" => {
+                                #nested_conflict_check
+                                self.path_updates.insert(path, value);
+                            }
+                            "$setOnInsert" => {
+                                self.insert_updates.insert(path, value);
+                            }
+                            _ => self.operator_updates.push((operator.clone(), path, value)),
+                        }
+                        staged = true;
+                    }
+                }
+                if staged {
+                    for filter in array_filters {
+                        let filter = filter
+                            .into_iter()
+                            .map(|(key, value)| match key.split_once('.') {
+                                Some((identifier, rest)) => {
+                                    (format!("{}.{}", rename(identifier), rest), value)
+                                }
+                                None => (rename(&key), value),
+                            })
+                            .collect();
+                        self.array_filters.push(filter);
+                    }
+                }
+                staged
+            }
+
+            /// Builds the MongoDB update document based on the configured operations.
+            ///
+            /// # Returns
+            ///
+            /// Result containing the update document or a MongoDB error
+            pub fn build(self) -> Result<bson::Document, mongodb::error::Error> {
+                self.build_with_options().map(|(update, _)| update)
+            }
+
+            /// Appends a raw stage to the pipeline returned by `build_pipeline`, e.g.
+            /// a `$set` of a computed field such as
+            /// `{ "$set": { "full_name": { "$concat": ["$first", " ", "$last"] } } }`.
+            ///
+            /// # Arguments
+            ///
+            /// * `stage` - The pipeline stage, run after the typed operations
+            ///
+            /// # Returns
+            ///
+            /// The builder instance
+            pub fn add_stage(mut self, stage: bson::Document) -> Self {
+                self.stages.push(stage);
+                self
+            }
 
-        impl #name {
-            /// Creates a new update builder for this struct.
-            pub fn update_builder() -> #builder_name {
-                #builder_name {
-                    path_updates: std::collections::HashMap::new(),
-                    ..Default::default()
+            /// Builds an update pipeline (MongoDB 4.2+): the staged `$set` and `$unset`
+            /// operations as leading stages, followed by the stages added with
+            /// `add_stage`.
+            ///
+            /// Set values are wrapped in `$literal`, so strings starting with `$` are
+            /// not read as field paths and documents replace the stored value as they
+            /// would in a regular update.
+            ///
+            /// # Returns
+            ///
+            /// Result containing the pipeline stages or a MongoDB error if an operator
+            /// other than `$set`/`$unset`, or array filters, were staged, as update
+            /// pipelines cannot express them
+            pub fn build_pipeline(mut self) -> Result<Vec<bson::Document>, mongodb::error::Error> {
+                let stages = std::mem::take(&mut self.stages);
+                let (update, array_filters) = self.build_collecting(None)?;
+                if !array_filters.is_empty() {
+                    return Err(mongodb::error::Error::custom(
+                        "update pipelines do not support arrayFilters".to_string()
+                    ));
                 }
-            }
-        }
 
-        impl #builder_name {
-            #(#builder_methods)*
+                let mut pipeline = Vec::new();
+                for (operator, paths) in update {
+                    let Some(paths) = paths.as_document() else {
+                        continue;
+                    };
+                    let mut stage = bson::Document::new();
+                    match operator.as_str() {
+                        "$set" => {
+                            let mut literals = bson::Document::new();
+                            for (path, value) in paths {
+                                let mut literal = bson::Document::new();
+                                literal.insert("$literal", value.clone());
+                                literals.insert(path, literal);
+                            }
+                            stage.insert("$set", literals);
+                        }
+                        "$unset" => {
+                            let paths: Vec<String> = paths.keys().cloned().collect();
+                            stage.insert("$unset", paths);
+                        }
+                        _ => {
+                            return Err(mongodb::error::Error::custom(format!(
+                                "`{}` cannot be expressed as an update pipeline stage",
+                                operator
+                            )));
+                        }
+                    }
+                    pipeline.push(stage);
+                }
+                pipeline.extend(stages);
+                Ok(pipeline)
+            }
 
-            /// Generic method for updating any field by path.
+            /// Builds the MongoDB update document and returns it together with the
+            /// accumulated `arrayFilters`.
             ///
-            /// This method allows you to set fields that might not be directly accessible
-            /// through the generated methods, such as nested fields or fields with special characters.
+            /// Equivalent to `build_with_options`; both the filters generated for
+            /// `update_<field>_by_id` and those added through `array_filter` are returned.
             ///
-            /// # Arguments
+            /// # Returns
             ///
-            /// * `field_path` - The dot notation path to the field
-            /// * `value` - The value to set for the field
+            /// Result containing the update document and array filters or a MongoDB error
+            pub fn build_with_array_filters(
+                self
+            ) -> Result<(bson::Document, Vec<bson::Document>), mongodb::error::Error> {
+                self.build_with_options()
+            }
+
+            /// Builds the MongoDB update document along with the `arrayFilters`
+            /// required by any positional `$[identifier]` paths that were staged.
+            ///
+            /// The filters must be passed through `UpdateOptions::array_filters`
+            /// since they are not part of the update document itself.
             ///
             /// # Returns
             ///
-            /// Result containing the builder instance or a MongoDB error
-            pub fn set_field<T: serde::Serialize>(
-                mut self,
-                field_path: &str,
-                value: T
-            ) -> Result<Self, mongodb::error::Error> {
-                self.path_updates.insert(field_path.to_string(), bson::to_bson(&value)?);
-                Ok(self)
+            /// Result containing the update document and array filters or a MongoDB error
+            pub fn build_with_options(
+                self
+            ) -> Result<(bson::Document, Vec<bson::Document>), mongodb::error::Error> {
+                self.build_collecting(None)
             }
 
-            /// Builds the MongoDB update document based on the configured operations.
+            /// Builds the MongoDB update document, skipping fields whose values
+            /// fail to serialize instead of aborting the whole update.
+            ///
+            /// This trades data integrity for resilience: the resulting update is
+            /// partial, so documents may end up with only some of the intended
+            /// changes. Only use it where that is acceptable, e.g. bulk ingestion,
+            /// and inspect the returned failures. If a container rule such as
+            /// `require_together` is violated, nothing is staged: the document is
+            /// empty and the violation is reported under the missing field.
             ///
             /// # Returns
             ///
-            /// Result containing the update document or a MongoDB error
-            pub fn build(self) -> Result<bson::Document, mongodb::error::Error> {
-                use bson::{doc, Document};
+            /// The update document and the document keys that failed, with their errors
+            pub fn build_lossy(self) -> (bson::Document, Vec<(String, mongodb::error::Error)>) {
+                let mut failures = Vec::new();
+                let (update, _) = self.build_collecting(Some(&mut failures)).unwrap_or_default();
+                (update, failures)
+            }
+
+            /// Builds the update, collecting serialization failures into `failures`
+            /// when given instead of returning the first one.
+            fn build_collecting(
+                self,
+                mut failures: Option<&mut Vec<(String, mongodb::error::Error)>>
+            ) -> Result<(bson::Document, Vec<bson::Document>), mongodb::error::Error> {
+                use bson::Document;
+                #conflicts_check
+                if let Some((_, error)) = self.nested_failures.first() {
+                    match failures.as_deref_mut() {
+                        Some(failures) => failures.extend(self.nested_failures.iter().cloned()),
+                        None => return Err(error.clone()),
+                    }
+                }
                 let mut update = Document::new();
                 let mut set_doc = Document::new();
                 let mut push_doc = Document::new();
                 let mut pull_doc = Document::new();
+                let mut pull_all_doc = Document::new();
+                let mut add_to_set_doc = Document::new();
+                let mut inc_doc = Document::new();
+                let mut mul_doc = Document::new();
+                let mut bit_doc = Document::new();
+                let mut min_doc = Document::new();
+                let mut max_doc = Document::new();
+                let mut set_on_insert_doc = Document::new();
+                let mut current_date_doc = Document::new();
+                let mut unset_doc: Document = self
+                    .unset_paths
+                    .iter()
+                    .map(|path| (path.clone(), bson::Bson::String(String::new())))
+                    .collect();
 
                 #(#set_conversions)*
                 #(#push_conversions)*
                 #(#pull_conversions)*
+                #(#add_to_set_conversions)*
+                #(#inc_conversions)*
+                #(#mul_conversions)*
+                #(#bit_conversions)*
+                #(#bound_conversions)*
+                #(#insert_conversions)*
+                #(#current_date_conversions)*
+                #(#unset_conversions)*
+
+                if self.deep_merge {
+                    set_doc = Self::merge_dotted_paths(set_doc);
+                }
 
                 if !set_doc.is_empty() {
                     update.insert("$set", set_doc);
@@ -384,8 +3282,249 @@ pub fn derive_mongo_update_builder(input: TokenStream) -> TokenStream {
                 if !pull_doc.is_empty() {
                     update.insert("$pull", pull_doc);
                 }
+                if !pull_all_doc.is_empty() {
+                    update.insert("$pullAll", pull_all_doc);
+                }
+                if !add_to_set_doc.is_empty() {
+                    update.insert("$addToSet", add_to_set_doc);
+                }
+                if !inc_doc.is_empty() {
+                    update.insert("$inc", inc_doc);
+                }
+                if !mul_doc.is_empty() {
+                    update.insert("$mul", mul_doc);
+                }
+                if !bit_doc.is_empty() {
+                    update.insert("$bit", bit_doc);
+                }
+                if !min_doc.is_empty() {
+                    update.insert("$min", min_doc);
+                }
+                if !max_doc.is_empty() {
+                    update.insert("$max", max_doc);
+                }
+                if !current_date_doc.is_empty() {
+                    update.insert("$currentDate", current_date_doc);
+                }
+                if !unset_doc.is_empty() {
+                    update.insert("$unset", unset_doc);
+                }
+
+                for (path, value) in &self.insert_updates {
+                    set_on_insert_doc.insert(path, value.clone());
+                }
+                if !set_on_insert_doc.is_empty() {
+                    update.insert("$setOnInsert", set_on_insert_doc);
+                }
+
+                for (operator, path, value) in &self.operator_updates {
+                    let paths = update
+                        .entry(operator.clone())
+                        .or_insert_with(|| bson::Bson::Document(Document::new()));
+                    if let bson::Bson::Document(paths) = paths {
+                        paths.insert(path.clone(), value.clone());
+                    }
+                }
+
+                for operator in &self.custom_operators {
+                    operator(&mut update);
+                }
+
+                // Looked up by the standard operator keys, before any are renamed
+                if self.for_upsert {
+                    update = Self::without_insert_conflicts(update);
+                }
+
+                #rule_checks
+
+                if !self.operator_keys.is_empty() {
+                    update = update
+                        .into_iter()
+                        .map(|(operator, value)| match self.operator_keys.get(&operator) {
+                            Some(key) => (key.clone(), value),
+                            None => (operator, value),
+                        })
+                        .collect();
+                }
+
+                // MongoDB rejects empty operator blocks such as `$push: {}`
+                update = update
+                    .into_iter()
+                    .filter(|(_, paths)| !matches!(paths, bson::Bson::Document(paths) if paths.is_empty()))
+                    .collect();
+
+                Ok((update, self.array_filters))
+            }
+
+            /// Builds the update once so it can be reused without re-serializing
+            /// the staged values, e.g. when applying it to many collections.
+            ///
+            /// # Returns
+            ///
+            /// Result containing the frozen update or a MongoDB error
+            pub fn freeze(self) -> Result<#frozen_update_name, mongodb::error::Error> {
+                let (update, array_filters) = self.build_with_options()?;
+                Ok(#frozen_update_name { update, array_filters })
+            }
+
+            /// Builds the MongoDB update document along with a human-readable
+            /// summary containing one line per staged operation.
+            ///
+            /// # Returns
+            ///
+            /// Result containing the update document and summary lines or a MongoDB error
+            pub fn build_with_summary(
+                self
+            ) -> Result<(bson::Document, Vec<String>), mongodb::error::Error> {
+                let update = self.build()?;
+                let summary = Self::summarize(&update);
+                Ok((update, summary))
+            }
+
+            /// Describes each operation of a built update document.
+            fn summarize(update: &bson::Document) -> Vec<String> {
+                // Lists the values wrapped in an array modifier such as `$each`
+                fn values(value: &bson::Bson, modifier: &str) -> String {
+                    match value
+                        .as_document()
+                        .and_then(|doc| doc.get_array(modifier).ok())
+                        .or_else(|| value.as_array())
+                    {
+                        Some(values) => values
+                            .iter()
+                            .map(|value| value.to_string())
+                            .collect::<Vec<_>>()
+                            .join(", "),
+                        None => value.to_string(),
+                    }
+                }
+
+                let mut summary = Vec::new();
+                for (operator, fields) in update {
+                    let Some(fields) = fields.as_document() else {
+                        continue;
+                    };
+                    for (key, value) in fields {
+                        summary.push(match operator.as_str() {
+                            "$set" => format!("set {}", key),
+                            "$push" => format!("added {} to {}", values(value, "$each"), key),
+                            "$pull" => format!("removed {} from {}", values(value, "$in"), key),
+                            "$pullAll" => format!("removed {} from {}", values(value, "$in"), key),
+                            "$addToSet" => {
+                                format!("added {} to set {}", values(value, "$each"), key)
+                            }
+                            "$inc" => format!("incremented {} by {}", key, value),
+                            "$mul" => format!("multiplied {} by {}", key, value),
+                            "$bit" => format!("applied {} to {}", value, key),
+                            "$min" => format!("lowered {} to at most {}", key, value),
+                            "$max" => format!("raised {} to at least {}", key, value),
+                            "$currentDate" => format!("set {} to the current date", key),
+                            "$setOnInsert" => format!("set {} on insert", key),
+                            "$unset" => format!("unset {}", key),
+                            _ => format!("{} {}", operator, key),
+                        });
+                    }
+                }
+                summary
+            }
+
+            /// Builds an update ready to be used with `upsert(true)`.
+            ///
+            /// The `$setOnInsert` block staged through `set_on_insert_field` is
+            /// included, minus any path also present in `$set`: MongoDB rejects
+            /// such conflicts, and `$set` applies to inserted documents anyway.
+            ///
+            /// # Returns
+            ///
+            /// Result containing the update document or a MongoDB error
+            pub fn build_for_upsert(mut self) -> Result<bson::Document, mongodb::error::Error> {
+                self.for_upsert = true;
+                self.build()
+            }
+
+            /// Removes `$setOnInsert` paths that are also staged in `$set`.
+            fn without_insert_conflicts(mut update: bson::Document) -> bson::Document {
+                let set_keys: Vec<String> = match update.get_document("$set") {
+                    Ok(set_doc) => set_doc.keys().cloned().collect(),
+                    Err(_) => return update,
+                };
+                if let Ok(set_on_insert_doc) = update.get_document_mut("$setOnInsert") {
+                    for key in &set_keys {
+                        set_on_insert_doc.remove(key);
+                    }
+                    if set_on_insert_doc.is_empty() {
+                        update.remove("$setOnInsert");
+                    }
+                }
+                update
+            }
+
+            /// Builds the update and applies it to the first document matching `filter`.
+            ///
+            /// Any staged array filters are passed through `UpdateOptions`.
+            ///
+            /// # Arguments
+            ///
+            /// * `collection` - The collection to update
+            /// * `filter` - The query selecting the document to update
+            ///
+            /// # Returns
+            ///
+            /// Result containing the driver's `UpdateResult` or a MongoDB error
+            pub async fn apply<Model>(
+                self,
+                collection: &mongodb::Collection<Model>,
+                filter: bson::Document
+            ) -> Result<mongodb::results::UpdateResult, mongodb::error::Error>
+            where
+                Model: Send + Sync,
+            {
+                #apply_one
+            }
+
+            /// Builds the update and applies it to every document matching `filter`.
+            ///
+            /// Any staged array filters are passed through `UpdateOptions`.
+            ///
+            /// # Arguments
+            ///
+            /// * `collection` - The collection to update
+            /// * `filter` - The query selecting the documents to update
+            ///
+            /// # Returns
+            ///
+            /// Result containing the driver's `UpdateResult` or a MongoDB error
+            pub async fn apply_many<Model>(
+                self,
+                collection: &mongodb::Collection<Model>,
+                filter: bson::Document
+            ) -> Result<mongodb::results::UpdateResult, mongodb::error::Error>
+            where
+                Model: Send + Sync,
+            {
+                #apply_many
+            }
 
-                Ok(update)
+            /// Builds the update with `build_for_upsert` and applies it to the first
+            /// document matching `filter`, inserting a new document if none matches.
+            ///
+            /// # Arguments
+            ///
+            /// * `collection` - The collection to update
+            /// * `filter` - The query selecting the document to update
+            ///
+            /// # Returns
+            ///
+            /// Result containing the driver's `UpdateResult` or a MongoDB error
+            pub async fn apply_upsert<Model>(
+                self,
+                collection: &mongodb::Collection<Model>,
+                filter: bson::Document
+            ) -> Result<mongodb::results::UpdateResult, mongodb::error::Error>
+            where
+                Model: Send + Sync,
+            {
+                #apply_upsert
             }
         }
     };
@@ -402,7 +3541,21 @@ pub fn derive_mongo_update_builder(input: TokenStream) -> TokenStream {
 ///
 /// A comma-separated list of `field: "Type"` pairs, where:
 /// - `field` is the name of the nested field in the parent struct
-/// - `"Type"` is the type of the nested field (must implement `MongoOperations`)
+/// - `"Type"` is the type of the nested field (must implement `MongoOperations`),
+///   either a bare name or a full path such as `"crate::models::Address"`. It
+///   may be omitted (`field` alone) to use the type the field is declared with
+///
+/// Nested types may declare nested fields of their own, so that `with_<field>`
+/// closures chain down to any depth, e.g. `address.geo.lat`.
+///
+/// When the field is a `Vec` of the nested type, an `update_<field>_by_id`
+/// method is generated instead, targeting the element whose `id` matches
+/// through `arrayFilters` (see `build_with_options`). When it is a `HashMap` or
+/// `BTreeMap` of the nested type, `with_<field>` takes the entry's key in addition
+/// to the closure and stages paths under `field.key.`.
+///
+/// For other fields, `with_<field>_or_null` is generated alongside `with_<field>`,
+/// setting the whole nested document to null when the closure stages nothing.
 ///
 /// # Example
 ///
@@ -430,24 +3583,157 @@ pub fn mongo_nested_fields(args: TokenStream, input: TokenStream) -> TokenStream
     let input = parse_macro_input!(input as DeriveInput);
     let parent_name = &input.ident;
     let builder_name = format_ident!("{}UpdateBuilder", parent_name);
+    let serialize_generics = with_serialize_bounds(&input.generics);
+    let (impl_generics, ty_generics, where_clause) = serialize_generics.split_for_impl();
 
     // Parse nested field declarations
     let nested_fields = parse_macro_input!(args as NestedFieldsArgs);
     let mut nested_methods = Vec::new();
+    let rename_all = serde_name(&input.attrs, "rename_all");
+
+    for (field_name_ident, type_name) in nested_fields.pairs {
+        let field_name = field_name_ident.to_string();
+        let type_name = match type_name
+            .or_else(|| find_field_type(&input, &field_name).and_then(nested_type_path))
+        {
+            Some(type_name) => type_name,
+            None => {
+                return syn::Error::new(
+                    field_name_ident.span(),
+                    format!(
+                        "cannot infer the nested type of `{}`; declare it as `{}: \"Type\"`",
+                        field_name, field_name
+                    ),
+                )
+                .to_compile_error()
+                .into();
+            }
+        };
+        let field_key = match find_field(&input, &field_name) {
+            Some(field) => serde_field_key(field, rename_all.as_deref()),
+            None => field_name.clone(),
+        };
+        let type_path = &type_name;
+        let nested_builder = builder_path(&type_name);
+
+        // Arrays of sub-documents are updated element-wise through arrayFilters
+        let is_array = find_field_type(&input, &field_name)
+            .and_then(get_vec_inner_type)
+            .is_some();
+        if is_array {
+            let by_id_method_name = format_ident!("update_{}_by_id", field_name);
+            nested_methods.push(quote! {
+                impl #impl_generics #builder_name #ty_generics #where_clause {
+                    /// Updates the array element whose `id` matches the given value.
+                    ///
+                    /// The nested builder's entries, under every operator, are staged
+                    /// under a filtered positional path (`field.$[e].sub_field`) and the matching
+                    /// `{ "e.id": id }` filter is recorded for `build_with_options`.
+                    ///
+                    /// # Arguments
+                    ///
+                    /// * `id` - The `id` of the array element to update
+                    /// * `f` - A function that configures the nested builder
+                    ///
+                    /// # Returns
+                    ///
+                    /// Result containing the parent builder instance or a MongoDB error
+                    pub fn #by_id_method_name<Id, Configure>(
+                        mut self,
+                        id: Id,
+                        f: Configure
+                    ) -> Result<Self, mongodb::error::Error>
+                    where
+                        Id: serde::Serialize,
+                        Configure: FnOnce(#nested_builder) -> #nested_builder,
+                    {
+                        let identifier = self.array_filter_identifier(&[]);
+                        let mut filter = bson::Document::new();
+                        filter.insert(
+                            format!("{}.{}", identifier, <#nested_builder>::ID_KEY),
+                            bson::to_bson(&id)?
+                        );
+
+                        // Reserve the identifier so forwarded nested filters are renamed around it
+                        let index = self.array_filters.len();
+                        self.array_filters.push(filter);
+                        let (doc, array_filters) =
+                            f(<#type_path>::update_builder()).build_with_options()?;
+                        let prefix = format!("{}.$[{}]", #field_key, identifier);
+                        let staged = self.merge_nested(
+                            stringify!(#by_id_method_name),
+                            &prefix,
+                            doc,
+                            array_filters
+                        );
 
-    for (field_name, type_name) in nested_fields.pairs {
-        let field_name_ident = format_ident!("{}", field_name);
-        let type_ident = format_ident!("{}", type_name);
-        let nested_builder = format_ident!("{}UpdateBuilder", type_name);
+                        // MongoDB rejects filters whose identifier is unused
+                        if !staged {
+                            self.array_filters.remove(index);
+                        }
+                        Ok(self)
+                    }
+                }
+            });
+        }
 
         // Generate method to work with the nested builder
         let with_method_name = format_ident!("with_{}", field_name);
-        nested_methods.push(quote! {
-            impl #builder_name {
+        let or_null_method_name = format_ident!("with_{}_or_null", field_name);
+        let is_map = find_field_type(&input, &field_name).is_some_and(is_map_type);
+        let with_method = if is_array {
+            // Array elements have no single prefix, only the direct path method applies
+            quote! {}
+        } else if is_map {
+            quote! {
+                /// Method to work with the nested update builder of a map entry.
+                ///
+                /// The nested builder's entries, under every operator, are staged under
+                /// the `field.key.` prefix of the entry stored at `key`. If the nested
+                /// builder fails to build, or `key` contains `.` or starts with `$` and
+                /// would be read as a path or an operator, the error is reported by this
+                /// builder's `build`.
+                ///
+                /// # Arguments
+                ///
+                /// * `key` - The key of the map entry to update
+                /// * `f` - A function that configures the nested builder
+                ///
+                /// # Returns
+                ///
+                /// The parent builder instance
+                pub fn #with_method_name<Configure>(mut self, key: &str, f: Configure) -> Self
+                where
+                    Configure: FnOnce(#nested_builder) -> #nested_builder,
+                {
+                    let builder = <#type_path>::update_builder();
+                    let prefix = format!("{}.{}", #field_key, key);
+                    if key.contains('.') || key.starts_with('$') {
+                        let error = mongodb::error::Error::custom(format!(
+                            "`{}` key `{}` must not contain `.` or start with `$`",
+                            stringify!(#with_method_name),
+                            key
+                        ));
+                        self.nested_failures.push((prefix, error));
+                        return self;
+                    }
+                    match f(builder).build_with_options() {
+                        Ok((doc, array_filters)) => {
+                            self.merge_nested(stringify!(#with_method_name), &prefix, doc, array_filters);
+                        }
+                        Err(error) => self.nested_failures.push((prefix, error)),
+                    }
+                    self
+                }
+            }
+        } else {
+            quote! {
                 /// Method to work with a nested update builder.
                 ///
                 /// This method allows you to use the update builder of a nested field
-                /// to create updates for nested documents.
+                /// to create updates for nested documents. Entries under every
+                /// operator are staged with the field's prefix. If the nested builder
+                /// fails to build, the error is reported by this builder's `build`.
                 ///
                 /// # Arguments
                 ///
@@ -456,47 +3742,76 @@ pub fn mongo_nested_fields(args: TokenStream, input: TokenStream) -> TokenStream
                 /// # Returns
                 ///
                 /// The parent builder instance
-                pub fn #with_method_name<F>(mut self, f: F) -> Self
+                pub fn #with_method_name<Configure>(mut self, f: Configure) -> Self
                 where
-                    F: FnOnce(#nested_builder) -> #nested_builder,
+                    Configure: FnOnce(#nested_builder) -> #nested_builder,
                 {
-                    let builder = #type_ident::update_builder();
+                    let builder = <#type_path>::update_builder();
                     let updated_builder = f(builder);
 
-                    // Clone the builder and call build to get the document
-                    if let Ok(doc) = updated_builder.clone().build() {
-                        // Insert each field from the nested document with the correct path
-                        for (key, value) in doc.iter() {
-                            if key == "$set" {
-                                if let bson::Bson::Document(set_doc) = value {
-                                    for (nested_key, nested_value) in set_doc.iter() {
-                                        let path = format!("{}.{}", #field_name, nested_key);
-                                        self.path_updates.insert(path, nested_value.clone());
-                                    }
-                                }
-                            }
+                    // Build the nested document and stage its paths under the field
+                    match updated_builder.build_with_options() {
+                        Ok((doc, array_filters)) => {
+                            self.merge_nested(stringify!(#with_method_name), #field_key, doc, array_filters);
                         }
+                        Err(error) => self.nested_failures.push((#field_key.to_string(), error)),
                     }
                     self
                 }
 
+                /// Like the nested builder method, but sets the whole nested document
+                /// to null when the closure stages nothing.
+                ///
+                /// # Arguments
+                ///
+                /// * `f` - A function that configures the nested builder
+                ///
+                /// # Returns
+                ///
+                /// The parent builder instance
+                pub fn #or_null_method_name<Configure>(mut self, f: Configure) -> Self
+                where
+                    Configure: FnOnce(#nested_builder) -> #nested_builder,
+                {
+                    let updated_builder = f(<#type_path>::update_builder());
+                    match updated_builder.clone().build() {
+                        Ok(doc) if doc.is_empty() => {
+                            if let Err(error) = self.set_nested_path(
+                                stringify!(#or_null_method_name),
+                                #field_key,
+                                bson::Bson::Null
+                            ) {
+                                self.nested_failures.push((#field_key.to_string(), error));
+                            }
+                            self
+                        }
+                        _ => self.#with_method_name(|_| updated_builder),
+                    }
+                }
+            }
+        };
+        nested_methods.push(quote! {
+            impl #impl_generics #builder_name #ty_generics #where_clause {
+                #with_method
+
                 /// Direct access to update a nested field by path.
                 ///
                 /// # Arguments
                 ///
-                /// * `nested_field` - The field name within the nested document
+                /// * `nested_field` - The field name within the nested document, led by
+                ///   an index or positional operator such as `$` for arrays
                 /// * `value` - The value to set for the nested field
                 ///
                 /// # Returns
                 ///
                 /// Result containing the parent builder instance or a MongoDB error
-                pub fn #field_name_ident<T: serde::Serialize>(
+                pub fn #field_name_ident<Value: serde::Serialize>(
                     mut self,
                     nested_field: &str,
-                    value: T
+                    value: Value
                 ) -> Result<Self, mongodb::error::Error> {
-                    let path = format!("{}.{}", #field_name, nested_field);
-                    self.path_updates.insert(path, bson::to_bson(&value)?);
+                    let path = format!("{}.{}", #field_key, nested_field);
+                    self.set_nested_path(stringify!(#field_name_ident), &path, bson::to_bson(&value)?)?;
                     Ok(self)
                 }
             }
@@ -512,3 +3827,186 @@ pub fn mongo_nested_fields(args: TokenStream, input: TokenStream) -> TokenStream
 
     TokenStream::from(result)
 }
+
+/// A derive macro that generates a query filter builder for a struct.
+///
+/// The generated `{Struct}Filter` has, for every named field, `eq_<field>`,
+/// `ne_<field>`, `gt_<field>`, `lt_<field>` and `in_<field>` methods emitting the
+/// matching comparison operator, plus `regex_<field>` for string fields. For
+/// `Vec<T>` fields the methods take `T`, matching arrays containing such an
+/// element. Conditions on the same field are combined into one document, and
+/// `User::filter()` creates an empty filter builder.
+///
+/// Generic structs are supported like with `MongoOperations`: the filter builder
+/// carries the struct's parameters, and its methods require every type parameter
+/// to implement `Serialize`.
+///
+/// # Example
+///
+/// ```rust
+/// use mongo_derive::MongoFilter;
+/// use serde::Serialize;
+///
+/// #[derive(Serialize, MongoFilter)]
+/// struct User {
+///     name: String,
+///     age: i32,
+/// }
+///
+/// # fn main() -> Result<(), mongodb::error::Error> {
+/// let filter = User::filter().eq_name("x").gt_age(18).build()?;
+/// # Ok(())
+/// # }
+/// ```
+#[proc_macro_derive(MongoFilter)]
+pub fn derive_mongo_filter(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    let name = &input.ident;
+    let filter_name = format_ident!("{}Filter", name);
+    let rename_all = serde_name(&input.attrs, "rename_all");
+    let generics = &input.generics;
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let serialize_generics = with_serialize_bounds(generics);
+    let serialize_where_clause = &serialize_generics.where_clause;
+
+    let fields = match named_fields(&input, "MongoFilter") {
+        Ok(fields) => fields,
+        Err(err) => return err.to_compile_error().into(),
+    };
+
+    let mut filter_methods = Vec::new();
+    for field in fields.iter() {
+        let field_name = field.ident.as_ref().unwrap();
+        let field_key = serde_field_key(field, rename_all.as_deref());
+        let value_type = get_vec_inner_type(&field.ty).unwrap_or(&field.ty);
+
+        let comparisons = [
+            ("eq", "$eq", "equals"),
+            ("ne", "$ne", "does not equal"),
+            ("gt", "$gt", "is greater than"),
+            ("lt", "$lt", "is less than"),
+        ];
+        for (prefix, operator, description) in comparisons {
+            let method_name = format_ident!("{}_{}", prefix, field_name);
+            let doc = format!(
+                "Matches documents where the field {} `value` (`{}`).",
+                description, operator
+            );
+            filter_methods.push(quote! {
+                #[doc = #doc]
+                pub fn #method_name(self, value: impl Into<#value_type>) -> Self {
+                    let value = bson::to_bson(&value.into());
+                    self.condition(#field_key, #operator, value)
+                }
+            });
+        }
+
+        let in_method_name = format_ident!("in_{}", field_name);
+        filter_methods.push(quote! {
+            /// Matches documents where the field equals any of `values` (`$in`).
+            pub fn #in_method_name<V: Into<#value_type>>(
+                self,
+                values: impl IntoIterator<Item = V>
+            ) -> Self {
+                let values: Vec<#value_type> = values.into_iter().map(Into::into).collect();
+                let values = bson::to_bson(&values);
+                self.condition(#field_key, "$in", values)
+            }
+        });
+
+        if is_string_type(value_type) {
+            let regex_method_name = format_ident!("regex_{}", field_name);
+            filter_methods.push(quote! {
+                /// Matches documents where the field matches the regular expression
+                /// `pattern` (`$regex`).
+                pub fn #regex_method_name(self, pattern: &str) -> Self {
+                    self.condition(#field_key, "$regex", Ok(bson::Bson::String(pattern.to_string())))
+                }
+            });
+        }
+    }
+
+    let expanded = quote! {
+        /// A query filter builder generated by the `MongoFilter` derive macro.
+        pub struct #filter_name #generics #where_clause {
+            conditions: Vec<(&'static str, &'static str, bson::Bson)>,
+            error: Option<mongodb::error::Error>,
+            model: std::marker::PhantomData<fn() -> #name #ty_generics>,
+        }
+
+        impl #impl_generics Default for #filter_name #ty_generics #where_clause {
+            fn default() -> Self {
+                Self {
+                    conditions: Vec::new(),
+                    error: None,
+                    model: std::marker::PhantomData,
+                }
+            }
+        }
+
+        impl #impl_generics Clone for #filter_name #ty_generics #where_clause {
+            fn clone(&self) -> Self {
+                Self {
+                    conditions: self.conditions.clone(),
+                    error: self.error.clone(),
+                    model: std::marker::PhantomData,
+                }
+            }
+        }
+
+        impl #impl_generics #name #ty_generics #serialize_where_clause {
+            /// Creates a new, empty filter builder for this type.
+            pub fn filter() -> #filter_name #ty_generics {
+                #filter_name::default()
+            }
+        }
+
+        impl #impl_generics #filter_name #ty_generics #serialize_where_clause {
+            /// Creates a new, empty filter builder.
+            pub fn new() -> Self {
+                Self::default()
+            }
+
+            #(#filter_methods)*
+
+            /// Records a condition, keeping the first serialization error for `build`.
+            fn condition(
+                mut self,
+                field: &'static str,
+                operator: &'static str,
+                value: Result<bson::Bson, bson::ser::Error>
+            ) -> Self {
+                match value {
+                    Ok(value) => self.conditions.push((field, operator, value)),
+                    Err(error) => {
+                        self.error.get_or_insert(error.into());
+                    }
+                }
+                self
+            }
+
+            /// Builds the MongoDB filter document from the configured conditions.
+            ///
+            /// # Returns
+            ///
+            /// Result containing the filter document or a MongoDB error
+            pub fn build(self) -> Result<bson::Document, mongodb::error::Error> {
+                if let Some(error) = self.error {
+                    return Err(error);
+                }
+                let mut filter = bson::Document::new();
+                for (field, operator, value) in self.conditions {
+                    let conditions = filter
+                        .entry(field.to_string())
+                        .or_insert_with(|| bson::Bson::Document(bson::Document::new()));
+                    if let bson::Bson::Document(conditions) = conditions {
+                        conditions.insert(operator, value);
+                    }
+                }
+                Ok(filter)
+            }
+        }
+    };
+
+    TokenStream::from(expanded)
+}
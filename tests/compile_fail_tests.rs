@@ -0,0 +1,7 @@
+//! Compile-time diagnostics reported by the derive macros
+
+#[test]
+fn test_compile_errors() {
+    let t = trybuild::TestCases::new();
+    t.compile_fail("tests/ui/*.rs");
+}
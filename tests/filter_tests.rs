@@ -0,0 +1,88 @@
+#[cfg(test)]
+mod tests {
+    use bson::doc;
+    use mongo_derive::{MongoFilter, MongoOperations};
+    use serde::{Deserialize, Serialize};
+
+    // Test Models
+    #[derive(Debug, Serialize, Deserialize, Clone, MongoOperations, MongoFilter)]
+    struct User {
+        #[mongo_ops(set)]
+        name: String,
+
+        #[mongo_ops(set)]
+        age: i32,
+
+        #[mongo_ops(set, push)]
+        tags: Vec<String>,
+    }
+
+    #[derive(Debug, Serialize, Deserialize, Clone, MongoFilter)]
+    #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+    struct Setting {
+        config_key: String,
+
+        #[serde(rename = "val")]
+        config_value: i32,
+    }
+
+    #[test]
+    fn test_comparison_filters() {
+        let filter = User::filter().eq_name("x").gt_age(18).build().unwrap();
+
+        // Verify each condition is emitted under its field
+        assert_eq!(
+            filter,
+            doc! { "name": { "$eq": "x" }, "age": { "$gt": 18 } }
+        );
+    }
+
+    #[test]
+    fn test_combined_field_conditions() {
+        let filter = User::filter()
+            .gt_age(18)
+            .lt_age(65)
+            .ne_name("admin")
+            .regex_name("^j")
+            .in_tags(["rust", "mongodb"])
+            .build()
+            .unwrap();
+
+        // Verify conditions on the same field share one document
+        assert_eq!(
+            filter,
+            doc! {
+                "age": { "$gt": 18, "$lt": 65 },
+                "name": { "$ne": "admin", "$regex": "^j" },
+                "tags": { "$in": ["rust", "mongodb"] },
+            }
+        );
+    }
+
+    #[test]
+    fn test_filter_alongside_update_builder() {
+        // Verify both derives can be used on the same struct
+        let filter = User::filter().eq_tags("rust").build().unwrap();
+        let update = User::update_builder()
+            .set_name("Jane".to_string())
+            .build()
+            .unwrap();
+        assert_eq!(filter, doc! { "tags": { "$eq": "rust" } });
+        assert_eq!(update, doc! { "$set": { "name": "Jane" } });
+    }
+
+    #[test]
+    fn test_renamed_fields() {
+        let filter = Setting::filter()
+            .eq_config_key("timeout")
+            .gt_config_value(30)
+            .build()
+            .unwrap();
+
+        // Verify the filter keys follow serde renames
+        assert_eq!(
+            filter,
+            doc! { "CONFIG_KEY": { "$eq": "timeout" }, "val": { "$gt": 30 } }
+        );
+    }
+}
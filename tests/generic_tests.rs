@@ -0,0 +1,105 @@
+// Generic structs and lifetime parameters must derive like concrete ones
+#[cfg(test)]
+mod tests {
+    use bson::doc;
+    use mongo_derive::{mongo_nested_fields, MongoFilter, MongoOperations};
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Debug, Serialize, Deserialize, Clone, MongoOperations, MongoFilter)]
+    struct Page<T> {
+        #[mongo_ops(push)]
+        items: Vec<T>,
+
+        #[mongo_ops(set)]
+        cursor: String,
+    }
+
+    #[derive(Debug, Serialize, Clone, MongoOperations, MongoFilter)]
+    struct Label<'a> {
+        #[mongo_ops(set)]
+        text: &'a str,
+
+        #[mongo_ops(set, inc)]
+        uses: i32,
+    }
+
+    #[mongo_nested_fields(page)]
+    #[derive(Debug, Serialize, Deserialize, Clone, MongoOperations)]
+    struct Feed<T: Clone> {
+        #[mongo_ops(set)]
+        name: String,
+
+        page: Page<String>,
+
+        #[mongo_ops(none)]
+        latest: Option<T>,
+    }
+
+    #[test]
+    fn test_generic_struct() {
+        let update = Page::<String>::update_builder()
+            .set_cursor("abc".to_string())
+            .build()
+            .unwrap();
+        assert_eq!(update, doc! { "$set": { "cursor": "abc" } });
+
+        // Verify a type parameter used only inside a pushed Vec
+        let update = Page::<i64>::update_builder()
+            .push_items(42)
+            .build()
+            .unwrap();
+        assert_eq!(update, doc! { "$push": { "items": { "$each": [42_i64] } } });
+    }
+
+    #[test]
+    fn test_lifetime_parameter() {
+        let text = String::from("urgent");
+        let update = Label::update_builder()
+            .set_text(&text)
+            .inc_uses(1)
+            .build()
+            .unwrap();
+        assert_eq!(
+            update,
+            doc! { "$set": { "text": "urgent" }, "$inc": { "uses": 1 } }
+        );
+    }
+
+    #[test]
+    fn test_generic_filter() {
+        let filter = Page::<i64>::filter()
+            .eq_items(42)
+            .eq_cursor("abc")
+            .build()
+            .unwrap();
+        assert_eq!(
+            filter,
+            doc! { "items": { "$eq": 42_i64 }, "cursor": { "$eq": "abc" } }
+        );
+
+        // Verify borrowed fields are filtered through the lifetime parameter
+        let text = String::from("urgent");
+        let filter = Label::filter()
+            .regex_text("^urg")
+            .ne_text(text.as_str())
+            .build()
+            .unwrap();
+        assert_eq!(
+            filter,
+            doc! { "text": { "$regex": "^urg", "$ne": "urgent" } }
+        );
+    }
+
+    #[test]
+    fn test_generic_parent_with_nested_fields() {
+        let update = Feed::<u8>::update_builder()
+            .set_name("news".to_string())
+            .with_page(|page| page.set_cursor("next".to_string()))
+            .build()
+            .unwrap();
+        assert_eq!(
+            update,
+            doc! { "$set": { "name": "news", "page.cursor": "next" } }
+        );
+    }
+}
@@ -0,0 +1,73 @@
+// Generated code must stay clippy-clean in crates with strict lint settings
+#![deny(clippy::all, clippy::pedantic, clippy::future_not_send)]
+
+#[cfg(test)]
+mod tests {
+    use mongo_derive::{mongo_nested_fields, MongoFilter, MongoOperations};
+    use serde::Serialize;
+    use std::collections::HashMap;
+
+    #[derive(Serialize, Clone, MongoOperations)]
+    pub struct Address {
+        #[mongo_ops(set)]
+        pub city: String,
+    }
+
+    #[mongo_nested_fields(address: "Address", addresses: "Address", by_label: "Address")]
+    #[derive(Serialize, Clone, MongoOperations, MongoFilter)]
+    #[mongo_ops(require_together("name", "visits"), on_conflict = "error")]
+    pub struct Contact {
+        #[mongo_ops(set, push, pull, add_to_set)]
+        pub tags: Vec<String>,
+
+        #[mongo_ops(set, inc, unset)]
+        pub visits: i32,
+
+        #[mongo_ops(set, owned = "String")]
+        pub name: &'static str,
+
+        pub manager: Option<Box<Contact>>,
+
+        pub address: Address,
+
+        pub addresses: Vec<Address>,
+
+        pub by_label: HashMap<String, Address>,
+    }
+
+    #[mongo_nested_fields(address)]
+    #[derive(Serialize, Clone, MongoOperations)]
+    pub struct Revision<'a, T> {
+        #[mongo_ops(set, push)]
+        pub changes: Vec<T>,
+
+        #[mongo_ops(set)]
+        pub author: &'a str,
+
+        pub address: Address,
+    }
+
+    #[test]
+    fn test_strict_lints_compile() {
+        let update = Contact::update_builder()
+            .set_name("Jane")
+            .inc_visits(1)
+            .build()
+            .unwrap();
+        assert_eq!(update.len(), 2);
+
+        let filter = Contact::filter()
+            .gt_visits(1)
+            .regex_name("^J")
+            .build()
+            .unwrap();
+        assert_eq!(filter.len(), 2);
+
+        let update = Revision::<i32>::update_builder()
+            .push_changes(1)
+            .set_author("Jane")
+            .build()
+            .unwrap();
+        assert_eq!(update.len(), 2);
+    }
+}
@@ -0,0 +1,105 @@
+// The `tracing` feature wraps `apply` calls in a span describing the update
+#[cfg(all(test, feature = "tracing"))]
+mod tests {
+    use mongo_derive::MongoOperations;
+    use serde::{Deserialize, Serialize};
+    use std::fmt::Debug;
+    use std::sync::{Arc, Mutex};
+    use tracing::field::{Field, Visit};
+    use tracing::span::{Attributes, Id, Record};
+    use tracing::{Event, Metadata, Subscriber};
+
+    #[derive(Debug, Serialize, Deserialize, Clone, MongoOperations)]
+    struct Article {
+        #[mongo_ops(set)]
+        title: String,
+
+        #[mongo_ops(set, inc)]
+        views: i64,
+    }
+
+    // A span captured with the fields recorded on it, formatted with `Debug`
+    #[derive(Debug, Default)]
+    struct CapturedSpan {
+        name: &'static str,
+        fields: Vec<(&'static str, String)>,
+    }
+
+    impl Visit for CapturedSpan {
+        fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+            self.fields.push((field.name(), format!("{:?}", value)));
+        }
+
+        fn record_str(&mut self, field: &Field, value: &str) {
+            self.fields.push((field.name(), value.to_string()));
+        }
+    }
+
+    // Subscriber keeping every span created while it is the default
+    #[derive(Clone, Default)]
+    struct CapturingSubscriber {
+        spans: Arc<Mutex<Vec<CapturedSpan>>>,
+    }
+
+    impl Subscriber for CapturingSubscriber {
+        fn enabled(&self, _metadata: &Metadata<'_>) -> bool {
+            true
+        }
+
+        fn new_span(&self, span: &Attributes<'_>) -> Id {
+            let mut captured = CapturedSpan {
+                name: span.metadata().name(),
+                ..CapturedSpan::default()
+            };
+            span.record(&mut captured);
+            let mut spans = self.spans.lock().unwrap();
+            spans.push(captured);
+            Id::from_u64(spans.len() as u64)
+        }
+
+        fn record(&self, span: &Id, values: &Record<'_>) {
+            let mut spans = self.spans.lock().unwrap();
+            let index = usize::try_from(span.into_u64()).unwrap() - 1;
+            values.record(&mut spans[index]);
+        }
+
+        fn record_follows_from(&self, _span: &Id, _follows: &Id) {}
+
+        fn event(&self, _event: &Event<'_>) {}
+
+        fn enter(&self, _span: &Id) {}
+
+        fn exit(&self, _span: &Id) {}
+    }
+
+    #[tokio::test]
+    async fn test_apply_span() {
+        let subscriber = CapturingSubscriber::default();
+        let _guard = tracing::subscriber::set_default(subscriber.clone());
+
+        // No server listens here, so the update fails once server selection times out
+        let client = mongodb::Client::with_uri_str(
+            "mongodb://127.0.0.1:9/?serverSelectionTimeoutMS=100&connectTimeoutMS=100",
+        )
+        .await
+        .unwrap();
+        let articles = client.database("blog").collection::<Article>("articles");
+        let result = Article::update_builder()
+            .set_title("Hello".to_string())
+            .inc_views(1)
+            .apply(&articles, bson::doc! { "slug": "hello" })
+            .await;
+        assert!(result.is_err());
+
+        // Verify the span is named after the struct and operation and records the update
+        let spans = subscriber.spans.lock().unwrap();
+        let span = spans
+            .iter()
+            .find(|span| span.name == "Article::apply")
+            .expect("apply span should be created");
+        assert!(span
+            .fields
+            .contains(&("collection", "articles".to_string())));
+        assert!(span.fields.contains(&("changes", "2".to_string())));
+    }
+}
@@ -0,0 +1,10 @@
+use mongo_derive::MongoOperations;
+use serde::Serialize;
+
+#[derive(Serialize, MongoOperations)]
+struct Account {
+    #[mongo_ops(bit)]
+    balance: f64,
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use mongo_derive::MongoOperations;
+use serde::Serialize;
+
+#[derive(Serialize, MongoOperations)]
+enum Status {
+    Active,
+    Archived,
+}
+
+fn main() {}
@@ -0,0 +1,21 @@
+use mongo_derive::MongoOperations;
+use serde::Serialize;
+
+#[derive(Clone, Copy, Serialize)]
+enum Role {
+    Guest = 0,
+}
+
+#[derive(Serialize, MongoOperations)]
+struct Account {
+    #[mongo_ops(set, discriminant = "i32", owned = "Role")]
+    role: Role,
+}
+
+#[derive(Serialize, MongoOperations)]
+struct Post {
+    #[mongo_ops(push, transform = "str::to_lowercase")]
+    tags: Vec<String>,
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use mongo_derive::MongoOperations;
+use serde::Serialize;
+
+#[derive(Serialize, MongoOperations)]
+struct Post {
+    #[mongo_ops(inc)]
+    title: String,
+}
+
+fn main() {}
@@ -0,0 +1,11 @@
+use mongo_derive::{mongo_nested_fields, MongoOperations};
+use serde::Serialize;
+
+#[mongo_nested_fields(location)]
+#[derive(Serialize, MongoOperations)]
+struct Venue {
+    #[mongo_ops(set)]
+    location: (f64, f64),
+}
+
+fn main() {}
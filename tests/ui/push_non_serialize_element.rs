@@ -0,0 +1,14 @@
+use mongo_derive::MongoOperations;
+use serde::Serialize;
+
+#[derive(Clone)]
+struct Tag;
+
+#[derive(Serialize, MongoOperations)]
+struct Post {
+    #[serde(skip)]
+    #[mongo_ops(push)]
+    tags: Vec<Tag>,
+}
+
+fn main() {}
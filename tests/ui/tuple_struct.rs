@@ -0,0 +1,7 @@
+use mongo_derive::MongoOperations;
+use serde::Serialize;
+
+#[derive(Serialize, MongoOperations)]
+struct Point(f64, f64);
+
+fn main() {}
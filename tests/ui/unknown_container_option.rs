@@ -0,0 +1,10 @@
+use mongo_derive::MongoOperations;
+use serde::Serialize;
+
+#[derive(Serialize, MongoOperations)]
+#[mongo_ops(on_conflcit = "error")]
+struct Counter {
+    count: i64,
+}
+
+fn main() {}
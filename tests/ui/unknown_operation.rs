@@ -0,0 +1,10 @@
+use mongo_derive::MongoOperations;
+use serde::Serialize;
+
+#[derive(Serialize, MongoOperations)]
+struct Counter {
+    #[mongo_ops(set, increment)]
+    count: i64,
+}
+
+fn main() {}
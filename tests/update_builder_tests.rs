@@ -1,10 +1,25 @@
+extern crate alloc;
+
 #[cfg(test)]
 mod tests {
     use bson::{doc, Bson, Document};
     use mongo_derive::{mongo_nested_fields, MongoOperations};
     use serde::{Deserialize, Serialize};
+    use std::borrow::Cow;
+    use std::collections::HashMap;
+    use std::time::{Duration, SystemTime};
     // Test Models
 
+    #[derive(Debug, Serialize, Deserialize, Clone, MongoOperations)]
+    struct Geo {
+        #[mongo_ops(set)]
+        lat: f64,
+
+        #[mongo_ops(set)]
+        lng: f64,
+    }
+
+    #[mongo_nested_fields(geo)]
     #[derive(Debug, Serialize, Deserialize, Clone, MongoOperations)]
     struct Address {
         #[mongo_ops(set)]
@@ -12,6 +27,8 @@ mod tests {
 
         #[mongo_ops(set)]
         city: String,
+
+        geo: Geo,
     }
 
     #[derive(Debug, Serialize, Deserialize, Clone, MongoOperations)]
@@ -26,23 +43,443 @@ mod tests {
     #[mongo_nested_fields(address: "Address", preferences: "Preferences")]
     #[derive(Debug, Serialize, Deserialize, Clone, MongoOperations)]
     struct User {
-        #[mongo_ops(set)]
+        #[mongo_ops(set, set_on_insert)]
         name: String,
 
-        #[mongo_ops(set)]
+        #[mongo_ops(set, unset)]
         email: String,
 
-        #[mongo_ops(set, push, pull)]
+        #[mongo_ops(set, push, pull, add_to_set)]
         tags: Vec<String>,
 
         #[mongo_ops(none)]
         password_hash: String,
 
+        #[mongo_ops(set, inc)]
+        login_count: i32,
+
+        #[mongo_ops(min, max)]
+        high_score: i32,
+
+        #[mongo_ops(bit)]
+        flags: i32,
+
         address: Address,
 
         preferences: Preferences,
     }
 
+    #[derive(Debug, Serialize, Deserialize, Clone, MongoOperations)]
+    struct Item {
+        #[mongo_ops(none)]
+        id: u32,
+
+        #[mongo_ops(set)]
+        status: String,
+    }
+
+    #[mongo_nested_fields(items: "Item")]
+    #[derive(Debug, Serialize, Deserialize, Clone, MongoOperations)]
+    struct Order {
+        #[mongo_ops(set)]
+        reference: String,
+
+        items: Vec<Item>,
+    }
+
+    #[derive(Debug, Serialize, Deserialize, Clone, MongoOperations)]
+    struct Batch {
+        #[serde(rename = "_id")]
+        #[mongo_ops(none)]
+        id: u32,
+
+        #[mongo_ops(set)]
+        label: String,
+
+        orders: Vec<Order>,
+    }
+
+    #[mongo_nested_fields(last_order: "Order", batches: "Batch")]
+    #[derive(Debug, Serialize, Deserialize, Clone, MongoOperations)]
+    struct Shipment {
+        last_order: Order,
+
+        batches: Vec<Batch>,
+    }
+
+    #[derive(Debug, Serialize, Deserialize, Clone, Copy)]
+    #[repr(u8)]
+    enum Role {
+        Guest = 0,
+        Admin = 2,
+    }
+
+    #[derive(Debug, Serialize, Deserialize, Clone, MongoOperations)]
+    struct Account {
+        #[mongo_ops(set, set_on_insert, min, max, discriminant = "i32")]
+        role: Role,
+    }
+
+    mod models {
+        use mongo_derive::MongoOperations;
+        use serde::{Deserialize, Serialize};
+
+        #[derive(Debug, Serialize, Deserialize, Clone, MongoOperations)]
+        pub struct Location {
+            #[mongo_ops(set)]
+            pub lat: f64,
+
+            #[mongo_ops(set)]
+            pub lng: f64,
+        }
+    }
+
+    #[mongo_nested_fields(location: "crate::tests::models::Location")]
+    #[derive(Debug, Serialize, Deserialize, Clone, MongoOperations)]
+    struct Store {
+        #[mongo_ops(set)]
+        name: String,
+
+        location: models::Location,
+    }
+
+    type Labels = Vec<String>;
+
+    #[derive(Debug, Serialize, Deserialize, Clone, MongoOperations)]
+    struct Ticket {
+        #[mongo_ops(set, push, pull, vec_inner = "String")]
+        labels: Labels,
+    }
+
+    #[derive(Debug, Serialize, Deserialize, Clone)]
+    struct Localized<T> {
+        en: T,
+        fr: T,
+    }
+
+    #[derive(Debug, Serialize, Deserialize, Clone, MongoOperations)]
+    struct Article {
+        #[mongo_ops(set, sub_paths = "en, fr")]
+        title: Localized<String>,
+    }
+
+    /// A profile whose fields carry doc comments and `cfg_attr`-wrapped attributes.
+    #[derive(Debug, Serialize, Deserialize, Clone, MongoOperations)]
+    struct Profile {
+        /// Display name, renamed only when the predicate holds.
+        #[cfg_attr(all(), serde(rename = "displayName"))]
+        #[mongo_ops(set)]
+        display_name: String,
+
+        /// Array operations enabled through an always-true predicate.
+        #[cfg_attr(all(), mongo_ops(set, push))]
+        badges: Vec<String>,
+
+        /// Exclusion behind an always-false predicate never applies.
+        #[cfg_attr(any(), mongo_ops(none))]
+        bio: String,
+    }
+
+    #[derive(Debug, Serialize, Deserialize, Clone, MongoOperations)]
+    struct Session {
+        #[mongo_ops(set)]
+        last_seen: i64,
+
+        #[mongo_ops(set)]
+        device: String,
+    }
+
+    #[mongo_nested_fields(sessions: "Session")]
+    #[derive(Debug, Serialize, Deserialize, Clone, MongoOperations)]
+    struct Device {
+        sessions: HashMap<String, Session>,
+    }
+
+    #[derive(Debug, Serialize, Deserialize, Clone, MongoOperations)]
+    struct Subscriber {
+        #[mongo_ops(set, set_on_insert, transform = "str::to_lowercase")]
+        email: String,
+    }
+
+    // Deliberately not `Clone`
+    #[derive(Debug, Serialize, Deserialize, MongoOperations)]
+    struct TreeNode {
+        #[mongo_ops(set)]
+        label: String,
+
+        #[mongo_ops(set)]
+        parent: Option<Box<TreeNode>>,
+    }
+
+    #[derive(Debug, Serialize, MongoOperations)]
+    struct Greeting {
+        #[mongo_ops(set, owned = "String")]
+        text: &'static str,
+
+        #[mongo_ops(set, owned = "Vec<String>")]
+        words: Cow<'static, [String]>,
+    }
+
+    #[derive(Debug, Serialize, Deserialize, Clone, MongoOperations)]
+    struct Revision {
+        #[mongo_ops(set)]
+        last_modified: SystemTime,
+
+        #[mongo_ops(set, min, max, set_on_insert)]
+        published_at: Option<SystemTime>,
+    }
+
+    macro_rules! playlist {
+        ($tracks:ty) => {
+            #[derive(Debug, Serialize, Deserialize, Clone, MongoOperations)]
+            struct Playlist {
+                #[mongo_ops(push)]
+                std_tracks: std::vec::Vec<String>,
+
+                #[mongo_ops(push)]
+                alloc_tracks: alloc::vec::Vec<String>,
+
+                #[mongo_ops(push)]
+                macro_tracks: $tracks,
+            }
+        };
+    }
+
+    playlist!(Vec<String>);
+
+    // Price struct whose amount and currency must change together
+    #[derive(Debug, Serialize, Deserialize, Clone, MongoOperations)]
+    #[mongo_ops(require_together("amount", "currency"))]
+    struct Price {
+        #[mongo_ops(set, inc)]
+        amount: i64,
+
+        #[mongo_ops(set)]
+        currency: String,
+
+        #[mongo_ops(set)]
+        note: String,
+    }
+
+    // Quote struct nesting prices whose builders may fail
+    #[mongo_nested_fields(price: "Price", alternatives: "Price")]
+    #[derive(Debug, Serialize, Deserialize, Clone, MongoOperations)]
+    struct Quote {
+        #[mongo_ops(set)]
+        label: String,
+
+        price: Price,
+
+        alternatives: HashMap<String, Price>,
+    }
+
+    // Subscription struct with serde-serialized enum fields
+    #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+    #[serde(rename_all = "lowercase")]
+    enum Status {
+        Active,
+        Archived,
+        #[serde(skip)]
+        Pending,
+    }
+
+    #[derive(Debug, Serialize, Deserialize, Clone, MongoOperations)]
+    struct Subscription {
+        #[mongo_ops(set)]
+        status: Status,
+
+        #[mongo_ops(set)]
+        previous_status: Option<Status>,
+
+        #[mongo_ops(push)]
+        transitions: Vec<Status>,
+    }
+
+    // Activity log struct keeping a capped array of sub-documents
+    #[derive(Debug, Serialize, Deserialize, Clone)]
+    struct Event {
+        ts: i64,
+        kind: String,
+    }
+
+    #[derive(Debug, Serialize, Deserialize, Clone, MongoOperations)]
+    struct ActivityLog {
+        #[mongo_ops(push)]
+        events: Vec<Event>,
+    }
+
+    // Inventory struct adjusting stock and prices arithmetically
+    #[derive(Debug, Serialize, Deserialize, Clone, MongoOperations)]
+    struct Inventory {
+        #[mongo_ops(inc)]
+        stock: i32,
+
+        #[mongo_ops(set, mul)]
+        price: f64,
+    }
+
+    // Counter struct with an unsigned field that may exceed BSON's range
+    #[derive(Debug, Serialize, Deserialize, Clone, MongoOperations)]
+    struct Counter {
+        #[mongo_ops(set)]
+        label: String,
+
+        #[mongo_ops(set)]
+        total: u64,
+    }
+
+    // Listing struct with optional fields for diffing
+    #[derive(Debug, Serialize, Deserialize, Clone, MongoOperations)]
+    struct Listing {
+        #[mongo_ops(set)]
+        title: String,
+
+        #[mongo_ops(set)]
+        subtitle: Option<String>,
+
+        #[mongo_ops(set, set_on_insert)]
+        discount: Option<i32>,
+
+        #[mongo_ops(set)]
+        archived_at: Option<String>,
+    }
+
+    // Draft struct diffing fields that became None to null
+    #[derive(Debug, Serialize, Deserialize, Clone, MongoOperations)]
+    #[mongo_ops(diff_none = "null")]
+    struct Draft {
+        #[mongo_ops(set)]
+        body: Option<String>,
+    }
+
+    // Timeline struct pruning timestamps by range
+    #[derive(Debug, Serialize, Deserialize, Clone, MongoOperations)]
+    struct Timeline {
+        #[mongo_ops(push, pull)]
+        events: Vec<i64>,
+    }
+
+    // Contact card struct with per-field None handling
+    #[derive(Debug, Serialize, Deserialize, Clone, MongoOperations)]
+    struct ContactCard {
+        #[mongo_ops(set)]
+        phone: Option<String>,
+
+        #[mongo_ops(set, on_none = "unset")]
+        fax: Option<String>,
+
+        #[mongo_ops(set, on_none = "skip")]
+        pager: Option<String>,
+    }
+
+    // Stats struct nested with non-$set operators
+    #[derive(Debug, Serialize, Deserialize, Clone, MongoOperations)]
+    struct Stats {
+        #[mongo_ops(inc)]
+        views: i64,
+
+        #[mongo_ops(push)]
+        referrers: Vec<String>,
+
+        #[mongo_ops(current_date)]
+        updated_at: bson::DateTime,
+    }
+
+    #[mongo_nested_fields(stats: "Stats")]
+    #[derive(Debug, Serialize, Deserialize, Clone, MongoOperations)]
+    struct Page {
+        #[mongo_ops(set)]
+        title: String,
+
+        stats: Stats,
+    }
+
+    // Venue struct with coordinate pairs
+    #[derive(Debug, Serialize, Deserialize, Clone, MongoOperations)]
+    struct Venue {
+        #[mongo_ops(set)]
+        location: (f64, f64),
+
+        #[mongo_ops(set, set_on_insert, tuple_fields = "lng, lat")]
+        entrance: (f64, f64),
+    }
+
+    // Category struct refining boxed and discriminant fields with options
+    #[derive(Debug, Serialize, Deserialize, Clone, MongoOperations)]
+    struct Category {
+        #[mongo_ops(set)]
+        label: String,
+
+        #[mongo_ops(set, on_none = "unset", transform = "lowercase_label")]
+        parent: Option<Box<Category>>,
+
+        #[mongo_ops(set, discriminant = "i32", transform = "promote_guest")]
+        role: Role,
+    }
+
+    fn lowercase_label(category: &Category) -> Category {
+        Category {
+            label: category.label.to_lowercase(),
+            ..category.clone()
+        }
+    }
+
+    fn promote_guest(role: &Role) -> Role {
+        match role {
+            Role::Guest => Role::Admin,
+            role => *role,
+        }
+    }
+
+    // Invoice struct rejecting paths staged twice
+    #[mongo_nested_fields(billing_address: "Address")]
+    #[derive(Debug, Serialize, Deserialize, Clone, MongoOperations)]
+    #[mongo_ops(on_conflict = "error")]
+    struct Invoice {
+        #[mongo_ops(set)]
+        number: String,
+
+        #[mongo_ops(set)]
+        total: i64,
+
+        #[mongo_ops(set, unset)]
+        memo: String,
+
+        billing_address: Address,
+    }
+
+    // Postal address struct with renamed inner fields
+    #[derive(Debug, Serialize, Deserialize, Clone, MongoOperations)]
+    #[serde(rename_all = "camelCase")]
+    struct PostalAddress {
+        #[mongo_ops(set)]
+        street_name: String,
+
+        #[serde(rename = "zip")]
+        #[mongo_ops(set)]
+        zip_code: String,
+    }
+
+    // Customer struct whose keys follow serde renames
+    #[mongo_nested_fields(postal_address: "PostalAddress")]
+    #[derive(Debug, Serialize, Deserialize, Clone, MongoOperations)]
+    #[serde(rename_all = "camelCase")]
+    struct Customer {
+        #[mongo_ops(set, push)]
+        phone_numbers: Vec<String>,
+
+        #[serde(rename(serialize = "zipCode", deserialize = "zip_code"))]
+        #[mongo_ops(set)]
+        zip_code: String,
+
+        postal_address: PostalAddress,
+    }
+
+    // Custom operator stamping the modification time, reusable across models
+    fn touch(update: &mut Document) {
+        update.insert("$currentDate", doc! { "updated_at": true });
+    }
+
     // Helper function to extract a document from a specific MongoDB operator
     fn get_operator_doc<'a>(doc: &'a Document, operator: &'a str) -> Option<&'a Document> {
         match doc.get(operator) {
@@ -51,6 +488,24 @@ mod tests {
         }
     }
 
+    // Compile-time check that the generated `apply` methods drive a collection
+    #[allow(dead_code)]
+    async fn apply_updates(users: &mongodb::Collection<User>) -> mongodb::error::Result<()> {
+        User::update_builder()
+            .set_name("John Doe".to_string())
+            .apply(users, doc! { "email": "john@example.com" })
+            .await?;
+        User::update_builder()
+            .push_tags("rust".to_string())
+            .apply_many(users, doc! {})
+            .await?;
+        User::update_builder()
+            .set_on_insert_field("name", "Anonymous")?
+            .apply_upsert(users, doc! { "email": "anon@example.com" })
+            .await?;
+        Ok(())
+    }
+
     #[test]
     fn test_basic_set_operations() {
         // Create a simple update
@@ -189,4 +644,1821 @@ mod tests {
             "2025-03-06"
         );
     }
+
+    #[test]
+    fn test_update_array_element_by_id() {
+        let (update, array_filters) = Order::update_builder()
+            .update_items_by_id(42, |builder| builder.set_status("shipped".to_string()))
+            .unwrap()
+            .build_with_options()
+            .unwrap();
+
+        // Verify the filtered positional path
+        let set_doc = get_operator_doc(&update, "$set").expect("$set operator should exist");
+        assert_eq!(
+            set_doc.get("items.$[e].status").unwrap().as_str().unwrap(),
+            "shipped"
+        );
+
+        // Verify the matching array filter
+        assert_eq!(array_filters, vec![doc! { "e.id": 42 }]);
+
+        // Verify the filter follows the element's renamed id key
+        let (_, array_filters) = Shipment::update_builder()
+            .update_batches_by_id(5, |builder| builder.set_label("late".to_string()))
+            .unwrap()
+            .build_with_options()
+            .unwrap();
+        assert_eq!(BatchUpdateBuilder::ID_KEY, "_id");
+        assert_eq!(array_filters, vec![doc! { "e._id": 5 }]);
+
+        // Verify array fields keep the direct path method
+        let update = Order::update_builder()
+            .items("0.status", "shipped")
+            .unwrap()
+            .build()
+            .unwrap();
+        assert_eq!(update, doc! { "$set": { "items.0.status": "shipped" } });
+    }
+
+    #[test]
+    fn test_nested_build_errors() {
+        // Verify an invalid nested builder fails the parent's build
+        assert!(Quote::update_builder()
+            .set_label("spring".to_string())
+            .with_price(|price| price.set_amount(5))
+            .build()
+            .is_err());
+        assert!(Quote::update_builder()
+            .with_price_or_null(|price| price.set_amount(5))
+            .build()
+            .is_err());
+        assert!(Quote::update_builder()
+            .with_alternatives("eur", |price| price.set_amount(5))
+            .build()
+            .is_err());
+
+        // Verify a lossy build keeps the other paths and reports the nested field
+        let (update, failures) = Quote::update_builder()
+            .set_label("spring".to_string())
+            .with_alternatives("eur", |price| price.set_amount(5))
+            .build_lossy();
+        assert_eq!(update, doc! { "$set": { "label": "spring" } });
+        assert_eq!(failures.len(), 1);
+        assert_eq!(failures[0].0, "alternatives.eur");
+    }
+
+    #[test]
+    fn test_nested_array_filters_forwarded() {
+        let (update, array_filters) = Shipment::update_builder()
+            .with_last_order(|order| {
+                order
+                    .update_items_by_id(3, |item| item.set_status("lost".to_string()))
+                    .unwrap()
+            })
+            .build_with_options()
+            .unwrap();
+
+        // Verify the nested builder's filter reaches the parent's options
+        assert_eq!(
+            update,
+            doc! { "$set": { "last_order.items.$[e].status": "lost" } }
+        );
+        assert_eq!(array_filters, vec![doc! { "e.id": 3 }]);
+
+        // Verify a nested identifier taken by the parent is renamed
+        let (update, array_filters) = Shipment::update_builder()
+            .update_batches_by_id(1, |batch| batch.set_label("first".to_string()))
+            .unwrap()
+            .with_last_order(|order| {
+                order
+                    .update_items_by_id(3, |item| item.set_status("lost".to_string()))
+                    .unwrap()
+            })
+            .build_with_options()
+            .unwrap();
+        assert_eq!(
+            update,
+            doc! {
+                "$set": {
+                    "batches.$[e].label": "first",
+                    "last_order.items.$[e1].status": "lost",
+                }
+            }
+        );
+        assert_eq!(
+            array_filters,
+            vec![doc! { "e._id": 1 }, doc! { "e1.id": 3 }]
+        );
+    }
+
+    #[test]
+    fn test_enum_fields() {
+        let update = Subscription::update_builder()
+            .set_status(Status::Active)
+            .set_previous_status(Status::Archived)
+            .push_transitions(Status::Active)
+            .build()
+            .unwrap();
+
+        // Verify variants are stored as their serialized strings
+        assert_eq!(
+            update,
+            doc! {
+                "$set": { "status": "active", "previous_status": "archived" },
+                "$push": { "transitions": { "$each": ["active"] } },
+            }
+        );
+
+        // Verify optional enums can be cleared
+        let update = Subscription::update_builder()
+            .set_previous_status_null()
+            .build()
+            .unwrap();
+        assert_eq!(update, doc! { "$set": { "previous_status": Bson::Null } });
+
+        // Verify variants excluded from serialization fail the build
+        assert!(Subscription::update_builder()
+            .set_status(Status::Pending)
+            .build()
+            .is_err());
+    }
+
+    #[test]
+    fn test_enum_discriminant_storage() {
+        let update = Account::update_builder()
+            .set_role(Role::Admin)
+            .build()
+            .unwrap();
+
+        // Verify the enum is stored as its integer discriminant
+        let set_doc = get_operator_doc(&update, "$set").expect("$set operator should exist");
+        assert_eq!(set_doc.get("role"), Some(&Bson::Int32(2)));
+
+        let update = Account::update_builder()
+            .set_role(Role::Guest)
+            .build()
+            .unwrap();
+        let set_doc = get_operator_doc(&update, "$set").expect("$set operator should exist");
+        assert_eq!(set_doc.get("role"), Some(&Bson::Int32(0)));
+
+        // Verify diff stages discriminant changes
+        let update = AccountUpdateBuilder::diff(
+            &Account { role: Role::Guest },
+            &Account { role: Role::Admin },
+        )
+        .unwrap()
+        .build()
+        .unwrap();
+        assert_eq!(update, doc! { "$set": { "role": 2 } });
+        let update = AccountUpdateBuilder::diff(
+            &Account { role: Role::Admin },
+            &Account { role: Role::Admin },
+        )
+        .unwrap()
+        .build()
+        .unwrap();
+        assert!(update.is_empty());
+
+        // Verify $min and $max compare the stored discriminant
+        let update = Account::update_builder()
+            .min_role(Role::Guest)
+            .max_role(Role::Admin)
+            .build()
+            .unwrap();
+        assert_eq!(
+            update,
+            doc! { "$min": { "role": 0 }, "$max": { "role": 2 } }
+        );
+    }
+
+    #[test]
+    fn test_manual_array_filters() {
+        let (update, array_filters) = User::update_builder()
+            .set_field("tags.$[tag]", "mongodb")
+            .unwrap()
+            .array_filter(doc! { "tag": "mongo" })
+            .build_with_array_filters()
+            .unwrap();
+
+        // Verify the positional path and its filter are returned together
+        let set_doc = get_operator_doc(&update, "$set").expect("$set operator should exist");
+        assert_eq!(
+            set_doc.get("tags.$[tag]").unwrap().as_str().unwrap(),
+            "mongodb"
+        );
+        assert_eq!(array_filters, vec![doc! { "tag": "mongo" }]);
+    }
+
+    #[test]
+    fn test_nested_type_path() {
+        let update = Store::update_builder()
+            .with_location(|builder| builder.set_lat(48.85).set_lng(2.35))
+            .build()
+            .unwrap();
+
+        // Verify the nested builder was resolved through its full path
+        let set_doc = get_operator_doc(&update, "$set").expect("$set operator should exist");
+        assert_eq!(
+            set_doc.get("location.lat").unwrap().as_f64().unwrap(),
+            48.85
+        );
+        assert_eq!(set_doc.get("location.lng").unwrap().as_f64().unwrap(), 2.35);
+    }
+
+    #[test]
+    fn test_set_many() {
+        let update = User::update_builder()
+            .set_many(&[
+                ("address.city", Bson::String("Paris".to_string())),
+                ("preferences.font_size", Bson::Int32(12)),
+            ])
+            .build()
+            .unwrap();
+
+        // Verify every pair was staged
+        let set_doc = get_operator_doc(&update, "$set").expect("$set operator should exist");
+        assert_eq!(set_doc.get_str("address.city").unwrap(), "Paris");
+        assert_eq!(set_doc.get_i32("preferences.font_size").unwrap(), 12);
+    }
+
+    #[test]
+    fn test_aliased_vec_inner_type() {
+        let update = Ticket::update_builder()
+            .push_labels("bug".to_string())
+            .pull_labels("triage".to_string())
+            .build()
+            .unwrap();
+
+        // Verify array operations are generated through the declared inner type
+        let push_doc = get_operator_doc(&update, "$push").expect("$push operator should exist");
+        assert_eq!(
+            push_doc.get_document("labels").unwrap(),
+            &doc! { "$each": ["bug"] }
+        );
+        let pull_doc = get_operator_doc(&update, "$pull").expect("$pull operator should exist");
+        assert_eq!(
+            pull_doc.get_document("labels").unwrap(),
+            &doc! { "$in": ["triage"] }
+        );
+    }
+
+    #[test]
+    fn test_deep_merge() {
+        let update = User::update_builder()
+            .set_field("address", doc! { "city": "Paris", "geo": { "lng": 2.35 } })
+            .unwrap()
+            .set_field("address.geo.lat", 48.85)
+            .unwrap()
+            .set_field("address.street", "Rue de Rivoli")
+            .unwrap()
+            .deep_merge()
+            .build()
+            .unwrap();
+
+        // Verify the dotted paths were folded into the staged sub-document
+        let set_doc = get_operator_doc(&update, "$set").expect("$set operator should exist");
+        assert_eq!(set_doc.len(), 1);
+        assert_eq!(
+            set_doc.get_document("address").unwrap(),
+            &doc! {
+                "city": "Paris",
+                "geo": { "lng": 2.35, "lat": 48.85 },
+                "street": "Rue de Rivoli",
+            }
+        );
+
+        // Verify a staged sub-document is merged into the existing one
+        let update = User::update_builder()
+            .set_field(
+                "address",
+                doc! { "city": "Paris", "geo": { "lat": 1, "lng": 2 } },
+            )
+            .unwrap()
+            .set_field("address.geo", doc! { "lat": 5 })
+            .unwrap()
+            .deep_merge()
+            .build()
+            .unwrap();
+        assert_eq!(
+            update,
+            doc! { "$set": { "address": { "city": "Paris", "geo": { "lat": 5, "lng": 2 } } } }
+        );
+    }
+
+    #[test]
+    fn test_build_with_summary() {
+        let (update, summary) = User::update_builder()
+            .set_name("John Doe".to_string())
+            .push_tags("rust".to_string())
+            .pull_tags("go".to_string())
+            .build_with_summary()
+            .unwrap();
+
+        // Verify one summary line per staged operation
+        assert_eq!(update.len(), 3);
+        assert_eq!(
+            summary,
+            vec![
+                "set name".to_string(),
+                "added \"rust\" to tags".to_string(),
+                "removed \"go\" from tags".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_wrapper_sub_paths() {
+        let update = Article::update_builder()
+            .set_title_en("Hello".to_string())
+            .set_title_fr("Bonjour".to_string())
+            .build()
+            .unwrap();
+
+        // Verify one path per declared sub-path
+        let set_doc = get_operator_doc(&update, "$set").expect("$set operator should exist");
+        assert_eq!(set_doc.get_str("title.en").unwrap(), "Hello");
+        assert_eq!(set_doc.get_str("title.fr").unwrap(), "Bonjour");
+
+        // The whole wrapper can still be set at once
+        let update = Article::update_builder()
+            .set_title(Localized {
+                en: "Bye".to_string(),
+                fr: "Au revoir".to_string(),
+            })
+            .build()
+            .unwrap();
+        let set_doc = get_operator_doc(&update, "$set").expect("$set operator should exist");
+        assert_eq!(
+            set_doc.get_document("title").unwrap(),
+            &doc! { "en": "Bye", "fr": "Au revoir" }
+        );
+    }
+
+    #[test]
+    fn test_builder_entry_points() {
+        let from_struct = User::update_builder().set_name("John Doe".to_string());
+        let from_new = UserUpdateBuilder::new().set_name("John Doe".to_string());
+        let from_default = UserUpdateBuilder::default().set_name("John Doe".to_string());
+
+        // Verify every entry point yields the same usable builder
+        let expected = from_struct.build().unwrap();
+        assert_eq!(from_new.build().unwrap(), expected);
+        assert_eq!(
+            from_default
+                .set_field("address.city", "Paris")
+                .unwrap()
+                .build()
+                .unwrap(),
+            doc! { "$set": { "name": "John Doe", "address.city": "Paris" } }
+        );
+        assert_eq!(expected, doc! { "$set": { "name": "John Doe" } });
+    }
+
+    #[test]
+    fn test_custom_operator_keys() {
+        let update = User::update_builder()
+            .set_name("John Doe".to_string())
+            .push_tags("rust".to_string())
+            .operator_key("$set", "$$set")
+            .build()
+            .unwrap();
+
+        // Verify only the overridden operator key changed
+        assert!(update.get("$set").is_none());
+        assert_eq!(
+            get_operator_doc(&update, "$$set").expect("overridden key should exist"),
+            &doc! { "name": "John Doe" }
+        );
+        assert!(get_operator_doc(&update, "$push").is_some());
+    }
+
+    #[test]
+    fn test_push_with_modifiers() {
+        let update = User::update_builder()
+            .push_tags_with(UserPushOptions {
+                values: vec!["rust".to_string(), "mongodb".to_string()],
+                position: Some(0),
+                slice: Some(-5),
+                sort: Some(Bson::Int32(1)),
+            })
+            .build()
+            .unwrap();
+
+        // Verify every set modifier is emitted alongside $each
+        let push_doc = get_operator_doc(&update, "$push").expect("$push operator should exist");
+        assert_eq!(
+            push_doc.get_document("tags").unwrap(),
+            &doc! {
+                "$each": ["rust", "mongodb"],
+                "$position": 0,
+                "$slice": -5,
+                "$sort": 1,
+            }
+        );
+
+        // Verify unset modifiers are omitted
+        let update = User::update_builder()
+            .push_tags_with(UserPushOptions {
+                values: vec!["rust".to_string()],
+                slice: Some(3),
+                ..Default::default()
+            })
+            .build()
+            .unwrap();
+        let push_doc = get_operator_doc(&update, "$push").expect("$push operator should exist");
+        assert_eq!(
+            push_doc.get_document("tags").unwrap(),
+            &doc! { "$each": ["rust"], "$slice": 3 }
+        );
+    }
+
+    #[test]
+    fn test_push_capped_sorted_array() {
+        let update = ActivityLog::update_builder()
+            .push_events_with(ActivityLogPushOptions {
+                values: vec![Event {
+                    ts: 42,
+                    kind: "login".to_string(),
+                }],
+                position: Some(0),
+                slice: Some(-50),
+                sort: Some(doc! { "ts": -1 }.into()),
+            })
+            .build()
+            .unwrap();
+
+        // Verify the last 50 events are kept, sorted by a sub-document field
+        let push_doc = get_operator_doc(&update, "$push").expect("$push operator should exist");
+        assert_eq!(
+            push_doc.get_document("events").unwrap(),
+            &doc! {
+                "$each": [{ "ts": 42_i64, "kind": "login" }],
+                "$position": 0,
+                "$slice": -50,
+                "$sort": { "ts": -1 },
+            }
+        );
+    }
+
+    #[test]
+    fn test_set_array_element() {
+        let (update, array_filters) = Order::update_builder()
+            .set_array_element("items", "elem", "qty", 5, doc! { "elem.sku": "A-1" })
+            .unwrap()
+            .build_with_options()
+            .unwrap();
+
+        // Verify the filtered positional path and its matching filter
+        assert_eq!(update, doc! { "$set": { "items.$[elem].qty": 5 } });
+        assert_eq!(array_filters, vec![doc! { "elem.sku": "A-1" }]);
+
+        // Verify filters on another identifier are rejected
+        assert!(Order::update_builder()
+            .set_array_element("items", "elem", "qty", 5, doc! { "other.sku": "A-1" })
+            .is_err());
+    }
+
+    #[test]
+    fn test_cfg_attr_fields() {
+        let update = Profile::update_builder()
+            .set_display_name("Jane".to_string())
+            .push_badges("early-adopter".to_string())
+            .set_bio("Rustacean".to_string())
+            .build()
+            .unwrap();
+
+        // Verify `cfg_attr`-wrapped `mongo_ops` attributes were honored
+        let push_doc = get_operator_doc(&update, "$push").expect("$push operator should exist");
+        assert!(push_doc.get("badges").is_some());
+        let set_doc = get_operator_doc(&update, "$set").expect("$set operator should exist");
+        assert_eq!(set_doc.get_str("bio").unwrap(), "Rustacean");
+
+        // Verify the `cfg_attr`-wrapped serde rename is applied to the key
+        assert_eq!(set_doc.get_str("displayName").unwrap(), "Jane");
+    }
+
+    #[test]
+    fn test_increment() {
+        let update = User::update_builder().inc_login_count(1).build().unwrap();
+
+        // Verify the increment is emitted under $inc
+        let inc_doc = update
+            .get_document("$inc")
+            .expect("$inc operator should exist");
+        assert_eq!(inc_doc, &doc! { "login_count": 1 });
+        assert!(!update.contains_key("$set"));
+    }
+
+    #[test]
+    fn test_checked_increment() {
+        let update = User::update_builder()
+            .inc_login_count_checked(10, 5)
+            .unwrap()
+            .build()
+            .unwrap();
+
+        // Verify the increment is staged when it cannot overflow
+        let inc_doc = get_operator_doc(&update, "$inc").expect("$inc operator should exist");
+        assert_eq!(inc_doc, &doc! { "login_count": 5 });
+
+        // Verify an increment past the type's bounds is rejected
+        assert!(User::update_builder()
+            .inc_login_count_checked(i32::MAX - 1, 5)
+            .is_err());
+    }
+
+    #[test]
+    fn test_keyed_map_entries() {
+        let update = Device::update_builder()
+            .with_sessions("abc123", |builder| {
+                builder
+                    .set_last_seen(1_700_000_000)
+                    .set_device("laptop".to_string())
+            })
+            .build()
+            .unwrap();
+
+        // Verify the entry's key is part of every staged path
+        let set_doc = get_operator_doc(&update, "$set").expect("$set operator should exist");
+        assert_eq!(
+            set_doc.get_i64("sessions.abc123.last_seen").unwrap(),
+            1_700_000_000
+        );
+        assert_eq!(set_doc.get_str("sessions.abc123.device").unwrap(), "laptop");
+
+        // Verify a key containing a dot fails the build
+        let result = Device::update_builder()
+            .with_sessions("abc.123", |builder| builder.set_last_seen(1))
+            .build();
+        assert_eq!(
+            result
+                .unwrap_err()
+                .get_custom::<String>()
+                .map(String::as_str),
+            Some("`with_sessions` key `abc.123` must not contain `.` or start with `$`")
+        );
+
+        // Verify a key read as an operator fails the build
+        assert!(Device::update_builder()
+            .with_sessions("$where", |builder| builder.set_last_seen(1))
+            .build()
+            .is_err());
+    }
+
+    #[test]
+    fn test_set_transform() {
+        let update = Subscriber::update_builder()
+            .set_email("Jane.Doe@Example.COM".to_string())
+            .build()
+            .unwrap();
+
+        // Verify the value was normalized before staging
+        let set_doc = get_operator_doc(&update, "$set").expect("$set operator should exist");
+        assert_eq!(set_doc.get_str("email").unwrap(), "jane.doe@example.com");
+    }
+
+    #[test]
+    fn test_build_for_upsert() {
+        let update = User::update_builder()
+            .set_email("jane@example.com".to_string())
+            .set_on_insert_field("name", "Anonymous")
+            .unwrap()
+            .set_on_insert_field("email", "placeholder@example.com")
+            .unwrap()
+            .build_for_upsert()
+            .unwrap();
+
+        // Verify $setOnInsert is merged without the paths already in $set
+        assert_eq!(
+            update,
+            doc! {
+                "$set": { "email": "jane@example.com" },
+                "$setOnInsert": { "name": "Anonymous" },
+            }
+        );
+
+        // Verify conflicts are still removed when the operator keys are renamed
+        let update = User::update_builder()
+            .operator_key("$set", "set")
+            .operator_key("$setOnInsert", "setOnInsert")
+            .set_email("jane@example.com".to_string())
+            .set_on_insert_field("email", "placeholder@example.com")
+            .unwrap()
+            .build_for_upsert()
+            .unwrap();
+        assert_eq!(update, doc! { "set": { "email": "jane@example.com" } });
+    }
+
+    #[test]
+    fn test_deeply_nested_fields() {
+        let update = User::update_builder()
+            .with_address(|address| {
+                address
+                    .set_city("Lyon".to_string())
+                    .with_geo(|geo| geo.set_lat(1.0))
+            })
+            .build()
+            .unwrap();
+
+        // Verify the grandchild path composes through both nested builders
+        let set_doc = get_operator_doc(&update, "$set").expect("$set operator should exist");
+        assert_eq!(set_doc.get_f64("address.geo.lat").unwrap(), 1.0);
+        assert_eq!(set_doc.get_str("address.city").unwrap(), "Lyon");
+    }
+
+    #[test]
+    fn test_set_on_insert_fields() {
+        let update = User::update_builder()
+            .set_email("jane@example.com".to_string())
+            .set_on_insert_name("Anonymous".to_string())
+            .build()
+            .unwrap();
+
+        // Verify $set and $setOnInsert are emitted separately
+        assert_eq!(
+            update,
+            doc! {
+                "$set": { "email": "jane@example.com" },
+                "$setOnInsert": { "name": "Anonymous" },
+            }
+        );
+
+        // Verify a field may be both set and set on insert
+        let update = User::update_builder()
+            .set_name("Jane".to_string())
+            .set_on_insert_name("Anonymous".to_string())
+            .build()
+            .unwrap();
+        assert_eq!(
+            update,
+            doc! { "$set": { "name": "Jane" }, "$setOnInsert": { "name": "Anonymous" } }
+        );
+    }
+
+    #[test]
+    fn test_set_on_insert_storage_options() {
+        // Verify discriminants are stored as integers by both operators
+        let update = Account::update_builder()
+            .set_role(Role::Admin)
+            .set_on_insert_role(Role::Admin)
+            .build()
+            .unwrap();
+        assert_eq!(
+            update,
+            doc! { "$set": { "role": 2 }, "$setOnInsert": { "role": 2 } }
+        );
+
+        // Verify values are transformed before being set on insert
+        let update = Subscriber::update_builder()
+            .set_on_insert_email("A@X.COM".to_string())
+            .build()
+            .unwrap();
+        assert_eq!(update, doc! { "$setOnInsert": { "email": "a@x.com" } });
+
+        // Verify tuples are stored as named sub-documents
+        let update = Venue::update_builder()
+            .set_on_insert_entrance((1.0, 2.0))
+            .build()
+            .unwrap();
+        assert_eq!(
+            update,
+            doc! { "$setOnInsert": { "entrance": { "lng": 1.0, "lat": 2.0 } } }
+        );
+
+        // Verify optional fields take the inner value
+        let update = Listing::update_builder()
+            .set_on_insert_discount(15)
+            .build()
+            .unwrap();
+        assert_eq!(update, doc! { "$setOnInsert": { "discount": 15 } });
+    }
+
+    #[test]
+    fn test_optional_boxed_fields() {
+        let parent = TreeNode {
+            label: "root".to_string(),
+            parent: None,
+        };
+        let update = TreeNode::update_builder()
+            .set_parent(parent)
+            .build()
+            .unwrap();
+
+        // Verify the setter takes the unwrapped type
+        let set_doc = get_operator_doc(&update, "$set").expect("$set operator should exist");
+        assert_eq!(
+            set_doc.get_document("parent").unwrap(),
+            &doc! { "label": "root", "parent": null }
+        );
+
+        // Verify clearing stores null
+        let update = TreeNode::update_builder().clear_parent().build().unwrap();
+        assert_eq!(update, doc! { "$set": { "parent": null } });
+    }
+
+    #[test]
+    fn test_options_on_boxed_and_discriminant_fields() {
+        // Verify clearing follows `on_none`
+        let update = Category::update_builder().clear_parent().build().unwrap();
+        assert_eq!(update, doc! { "$unset": { "parent": "" } });
+
+        // Verify boxed and discriminant values are transformed before being staged
+        let parent = Category {
+            label: "Root".to_string(),
+            parent: None,
+            role: Role::Guest,
+        };
+        let update = Category::update_builder()
+            .set_parent(parent)
+            .set_role(Role::Guest)
+            .build()
+            .unwrap();
+        assert_eq!(
+            update,
+            doc! {
+                "$set": {
+                    "parent": { "label": "root", "parent": null, "role": "Guest" },
+                    "role": 2,
+                }
+            }
+        );
+    }
+
+    #[test]
+    fn test_freeze() {
+        let frozen = Order::update_builder()
+            .set_reference("A-1".to_string())
+            .update_items_by_id(7, |builder| builder.set_status("packed".to_string()))
+            .unwrap()
+            .freeze()
+            .unwrap();
+
+        // Verify every copy matches the single build
+        let expected = doc! {
+            "$set": { "reference": "A-1", "items.$[e].status": "packed" }
+        };
+        for _ in 0..3 {
+            assert_eq!(frozen.document(), expected);
+            assert_eq!(frozen.array_filters(), vec![doc! { "e.id": 7 }]);
+        }
+    }
+
+    #[test]
+    fn test_owned_setter_inputs() {
+        let words = vec!["hello".to_string(), "world".to_string()];
+        let update = Greeting::update_builder()
+            .set_text("hello world")
+            .set_words(words.as_slice())
+            .build()
+            .unwrap();
+
+        // Verify borrowed inputs are converted to owned values
+        assert_eq!(
+            update,
+            doc! { "$set": { "text": "hello world", "words": ["hello", "world"] } }
+        );
+    }
+
+    #[test]
+    fn test_system_time_as_date() {
+        let after_epoch = SystemTime::UNIX_EPOCH + Duration::from_millis(1_700_000_000_123);
+        let update = Revision::update_builder()
+            .set_last_modified(after_epoch)
+            .build()
+            .unwrap();
+
+        // Verify the time is stored as a BSON date
+        let set_doc = get_operator_doc(&update, "$set").expect("$set operator should exist");
+        assert_eq!(
+            set_doc.get_datetime("last_modified").unwrap(),
+            &bson::DateTime::from_millis(1_700_000_000_123)
+        );
+
+        // Verify times before the epoch become negative dates
+        let before_epoch = SystemTime::UNIX_EPOCH - Duration::from_secs(60);
+        let update = Revision::update_builder()
+            .set_last_modified(before_epoch)
+            .build()
+            .unwrap();
+        let set_doc = get_operator_doc(&update, "$set").expect("$set operator should exist");
+        assert_eq!(
+            set_doc.get_datetime("last_modified").unwrap(),
+            &bson::DateTime::from_millis(-60_000)
+        );
+    }
+
+    #[test]
+    fn test_optional_system_time_as_date() {
+        let published_at = SystemTime::UNIX_EPOCH + Duration::from_millis(1_700_000_000_123);
+        let date = bson::DateTime::from_millis(1_700_000_000_123);
+        let update = Revision::update_builder()
+            .set_published_at(published_at)
+            .min_published_at(Some(published_at))
+            .max_published_at(None)
+            .set_on_insert_published_at(published_at)
+            .build()
+            .unwrap();
+
+        // Verify every operator stores the time as a BSON date and `None` as null
+        assert_eq!(
+            update,
+            doc! {
+                "$set": { "published_at": date },
+                "$min": { "published_at": date },
+                "$max": { "published_at": null },
+                "$setOnInsert": { "published_at": date },
+            }
+        );
+
+        // Verify the diff compares and stages the BSON dates
+        let old = Revision {
+            last_modified: SystemTime::UNIX_EPOCH,
+            published_at: None,
+        };
+        let new = Revision {
+            published_at: Some(published_at),
+            ..old.clone()
+        };
+        let update = RevisionUpdateBuilder::diff(&old, &new)
+            .unwrap()
+            .build()
+            .unwrap();
+        assert_eq!(update, doc! { "$set": { "published_at": date } });
+    }
+
+    #[test]
+    fn test_qualified_vec_paths() {
+        let update = Playlist::update_builder()
+            .push_std_tracks("intro".to_string())
+            .push_alloc_tracks("verse".to_string())
+            .push_macro_tracks("outro".to_string())
+            .build()
+            .unwrap();
+
+        // Verify array operations are generated for every spelling of Vec
+        let push_doc = get_operator_doc(&update, "$push").expect("$push operator should exist");
+        assert_eq!(
+            push_doc,
+            &doc! {
+                "std_tracks": { "$each": ["intro"] },
+                "alloc_tracks": { "$each": ["verse"] },
+                "macro_tracks": { "$each": ["outro"] },
+            }
+        );
+    }
+
+    #[test]
+    fn test_describe_generated_methods() {
+        // Verify every field method is listed with its operator
+        assert_eq!(
+            TicketUpdateBuilder::describe(),
+            &[
+                ("push_labels", "$push"),
+                ("push_many_labels", "$push"),
+                ("push_labels_with", "$push"),
+                ("sort_labels", "$push"),
+                ("pull_labels", "$pull"),
+                ("pull_labels_lt", "$pull"),
+                ("pull_labels_gt", "$pull"),
+                ("pull_labels_where", "$pull"),
+                ("pull_all_labels", "$pullAll"),
+                ("set_labels", "$set"),
+                ("set_labels_pre", "$set"),
+            ]
+        );
+
+        // Verify excluded fields are not described
+        assert!(UserUpdateBuilder::describe()
+            .iter()
+            .all(|(method, _)| !method.contains("password_hash")));
+        assert!(UserUpdateBuilder::describe().contains(&("inc_login_count_checked", "$inc")));
+    }
+
+    #[test]
+    fn test_require_together_rule() {
+        // Verify staging only one field of the group fails
+        let err = Price::update_builder().set_amount(100).build().unwrap_err();
+        assert_eq!(
+            err.get_custom::<String>().map(String::as_str),
+            Some("`currency` must be updated together with `amount`")
+        );
+        assert!(Price::update_builder().inc_amount(5).build().is_err());
+
+        // Verify the group may be staged through different operators
+        let update = Price::update_builder()
+            .inc_amount(5)
+            .set_currency("EUR".to_string())
+            .build()
+            .unwrap();
+        assert_eq!(
+            update,
+            doc! { "$set": { "currency": "EUR" }, "$inc": { "amount": 5_i64 } }
+        );
+
+        // Verify fields outside the group are unaffected
+        assert!(Price::update_builder()
+            .set_note("sale".to_string())
+            .build()
+            .is_ok());
+    }
+
+    #[test]
+    fn test_build_lossy() {
+        let builder = Counter::update_builder()
+            .set_label("events".to_string())
+            .set_total(u64::MAX);
+
+        // Verify the strict build fails on the unserializable field
+        assert!(builder.clone().build().is_err());
+
+        // Verify the lossy build keeps the other fields and reports the failure
+        let (update, failures) = builder.build_lossy();
+        assert_eq!(update, doc! { "$set": { "label": "events" } });
+        assert_eq!(failures.len(), 1);
+        assert_eq!(failures[0].0, "total");
+
+        // Verify rule violations drop the whole update
+        let (update, failures) = Price::update_builder().set_amount(100).build_lossy();
+        assert!(update.is_empty());
+        assert_eq!(failures[0].0, "currency");
+    }
+
+    #[test]
+    fn test_diff_option_transitions() {
+        let old = Listing {
+            title: "Lamp".to_string(),
+            subtitle: Some("Brass".to_string()),
+            discount: Some(10),
+            archived_at: None,
+        };
+        let new = Listing {
+            title: "Lamp".to_string(),
+            subtitle: None,
+            discount: Some(20),
+            archived_at: Some("2024-01-01".to_string()),
+        };
+
+        // Verify Some -> None unsets, and Some -> Some and None -> Some set
+        let update = ListingUpdateBuilder::diff(&old, &new)
+            .unwrap()
+            .build()
+            .unwrap();
+        let set_doc = get_operator_doc(&update, "$set").expect("$set operator should exist");
+        assert_eq!(set_doc.len(), 2);
+        assert_eq!(set_doc.get_i32("discount").unwrap(), 20);
+        assert_eq!(set_doc.get_str("archived_at").unwrap(), "2024-01-01");
+        assert_eq!(
+            update.get_document("$unset").unwrap(),
+            &doc! { "subtitle": "" }
+        );
+
+        // Verify identical instances produce an empty update
+        let update = ListingUpdateBuilder::diff(&new, &new)
+            .unwrap()
+            .build()
+            .unwrap();
+        assert!(update.is_empty());
+
+        // Verify fields that became None can be set to null instead
+        let old = Draft {
+            body: Some("Hello".to_string()),
+        };
+        let new = Draft { body: None };
+        let update = DraftUpdateBuilder::diff(&old, &new)
+            .unwrap()
+            .build()
+            .unwrap();
+        assert_eq!(update, doc! { "$set": { "body": Bson::Null } });
+    }
+
+    #[test]
+    fn test_pull_range_conditions() {
+        let update = Timeline::update_builder()
+            .pull_events_lt(1_700_000_000)
+            .build()
+            .unwrap();
+
+        // Verify the range condition is emitted under $pull
+        assert_eq!(
+            update,
+            doc! { "$pull": { "events": { "$lt": 1_700_000_000_i64 } } }
+        );
+
+        // Verify both bounds combine into one condition
+        let update = Timeline::update_builder()
+            .pull_events_gt(10)
+            .pull_events_lt(20)
+            .build()
+            .unwrap();
+        let pull_doc = get_operator_doc(&update, "$pull").expect("$pull operator should exist");
+        assert_eq!(
+            pull_doc.get_document("events").unwrap(),
+            &doc! { "$lt": 20_i64, "$gt": 10_i64 }
+        );
+
+        // Verify a value pull mixed with a range bound fails the build
+        let result = Timeline::update_builder()
+            .pull_events(5)
+            .pull_events_lt(0)
+            .build();
+        assert_eq!(
+            result
+                .unwrap_err()
+                .get_custom::<String>()
+                .map(String::as_str),
+            Some("`pull_events` cannot be combined with range pull conditions on `events`")
+        );
+        assert!(Timeline::update_builder()
+            .pull_events_gt(100)
+            .pull_events(5)
+            .build()
+            .is_err());
+    }
+
+    #[test]
+    fn test_on_none_handling() {
+        let update = ContactCard::update_builder()
+            .set_phone_null()
+            .set_fax_null()
+            .set_pager_null()
+            .build()
+            .unwrap();
+
+        // Verify None is set to null, unset or skipped per field
+        assert_eq!(
+            update,
+            doc! { "$set": { "phone": Bson::Null }, "$unset": { "fax": "" } }
+        );
+
+        // Verify Some values are set regardless of the None handling
+        let update = ContactCard::update_builder()
+            .set_fax("555-0100".to_string())
+            .set_pager("555-0101".to_string())
+            .build()
+            .unwrap();
+        assert_eq!(
+            update,
+            doc! { "$set": { "fax": "555-0100", "pager": "555-0101" } }
+        );
+    }
+
+    #[test]
+    fn test_option_setters() {
+        // Verify the setter takes the inner value
+        let update = Listing::update_builder()
+            .set_subtitle("Limited edition".to_string())
+            .set_discount(15)
+            .build()
+            .unwrap();
+        assert_eq!(
+            update,
+            doc! { "$set": { "subtitle": "Limited edition", "discount": 15 } }
+        );
+
+        // Verify null is set explicitly and untouched fields are left out
+        let update = Listing::update_builder()
+            .set_subtitle_null()
+            .build()
+            .unwrap();
+        assert_eq!(update, doc! { "$set": { "subtitle": Bson::Null } });
+    }
+
+    #[test]
+    fn test_model_name() {
+        // Verify builders expose the name of their struct
+        assert_eq!(UserUpdateBuilder::MODEL_NAME, "User");
+        assert_eq!(models::LocationUpdateBuilder::MODEL_NAME, "Location");
+    }
+
+    #[test]
+    fn test_push_many() {
+        let update = User::update_builder()
+            .push_many_tags(["a", "b", "c"].map(String::from))
+            .build()
+            .unwrap();
+
+        // Verify all values are pushed in a single $each
+        let push_doc = get_operator_doc(&update, "$push").expect("$push operator should exist");
+        let each_array = push_doc
+            .get_document("tags")
+            .unwrap()
+            .get_array("$each")
+            .unwrap();
+        assert_eq!(each_array.len(), 3);
+
+        // Verify repeated pushes append instead of replacing
+        let update = User::update_builder()
+            .push_tags("a".to_string())
+            .push_many_tags(vec!["b".to_string()])
+            .push_tags("c".to_string())
+            .build()
+            .unwrap();
+        assert_eq!(
+            update,
+            doc! { "$push": { "tags": { "$each": ["a", "b", "c"] } } }
+        );
+    }
+
+    #[test]
+    fn test_pull_all_and_conditions() {
+        let update = User::update_builder()
+            .pull_all_tags(vec!["a".to_string(), "b".to_string()])
+            .build()
+            .unwrap();
+
+        // Verify $pullAll carries the full array
+        assert_eq!(
+            update
+                .get_document("$pullAll")
+                .unwrap()
+                .get_array("tags")
+                .unwrap(),
+            &vec![Bson::from("a"), Bson::from("b")]
+        );
+
+        // Verify repeated pulls are folded into one $in
+        let update = User::update_builder()
+            .pull_tags("a".to_string())
+            .pull_tags("b".to_string())
+            .build()
+            .unwrap();
+        assert_eq!(update, doc! { "$pull": { "tags": { "$in": ["a", "b"] } } });
+
+        // Verify a match condition is emitted as is
+        let update = Timeline::update_builder()
+            .pull_events_where(doc! { "$gte": 100_i64 })
+            .build()
+            .unwrap();
+        assert_eq!(update, doc! { "$pull": { "events": { "$gte": 100_i64 } } });
+
+        // Verify a match condition mixed with other pull conditions fails the build
+        let result = Timeline::update_builder()
+            .pull_events(5)
+            .pull_events_where(doc! { "$gte": 100_i64 })
+            .build();
+        assert_eq!(
+            result
+                .unwrap_err()
+                .get_custom::<String>()
+                .map(String::as_str),
+            Some("`pull_events_where` cannot be combined with other pull conditions on `events`")
+        );
+        assert!(Timeline::update_builder()
+            .pull_events_lt(10)
+            .pull_events_where(doc! { "$gte": 100_i64 })
+            .build()
+            .is_err());
+
+        // Verify $pullAll may still be staged alongside
+        let update = Timeline::update_builder()
+            .pull_all_events([1, 2])
+            .pull_events_where(doc! { "$gte": 100_i64 })
+            .build()
+            .unwrap();
+        assert_eq!(
+            update,
+            doc! {
+                "$pull": { "events": { "$gte": 100_i64 } },
+                "$pullAll": { "events": [1_i64, 2_i64] },
+            }
+        );
+    }
+
+    #[test]
+    fn test_add_to_set() {
+        let update = User::update_builder()
+            .add_to_set_tags("rust".to_string())
+            .add_to_set_tags("mongodb".to_string())
+            .push_tags("news".to_string())
+            .pull_tags("old".to_string())
+            .build()
+            .unwrap();
+
+        // Verify values are de-duplicated through a single $each
+        assert_eq!(
+            update.get_document("$addToSet").unwrap(),
+            &doc! { "tags": { "$each": ["rust", "mongodb"] } }
+        );
+
+        // Verify the other array operators stay distinct
+        assert!(update.get_document("$push").is_ok());
+        assert!(update.get_document("$pull").is_ok());
+    }
+
+    #[test]
+    fn test_sort_in_place() {
+        let update = Timeline::update_builder().sort_events(-1).build().unwrap();
+
+        // Verify an empty $each is emitted with the sort
+        assert_eq!(
+            update,
+            doc! { "$push": { "events": { "$each": [], "$sort": -1 } } }
+        );
+
+        // Verify sorting combines with a slice to keep order and cap
+        let update = Timeline::update_builder()
+            .push_events_with(TimelinePushOptions {
+                slice: Some(-10),
+                ..Default::default()
+            })
+            .sort_events(1)
+            .build()
+            .unwrap();
+        assert_eq!(
+            update,
+            doc! { "$push": { "events": { "$each": [], "$slice": -10, "$sort": 1 } } }
+        );
+    }
+
+    #[test]
+    fn test_custom_operators() {
+        let update = User::update_builder()
+            .set_name("John".to_string())
+            .with_operator(touch)
+            .with_operator(|update: &mut Document| {
+                // Verify built-in operators are written before custom ones run
+                assert!(update.contains_key("$set"));
+                update.insert("$rename", doc! { "nickname": "alias" });
+            })
+            .build()
+            .unwrap();
+
+        // Verify custom operators are added after the built-in ones, in order
+        assert_eq!(
+            update,
+            doc! {
+                "$set": { "name": "John" },
+                "$currentDate": { "updated_at": true },
+                "$rename": { "nickname": "alias" },
+            }
+        );
+
+        // Verify the same operator is reused by another model's builder
+        let update = Order::update_builder()
+            .set_reference("A-1".to_string())
+            .with_operator(touch)
+            .build()
+            .unwrap();
+        assert_eq!(
+            update,
+            doc! {
+                "$set": { "reference": "A-1" },
+                "$currentDate": { "updated_at": true },
+            }
+        );
+    }
+
+    #[test]
+    fn test_nested_or_null() {
+        // Verify an empty nested update nulls the sub-document
+        let update = User::update_builder()
+            .with_address_or_null(|builder| builder)
+            .build()
+            .unwrap();
+        assert_eq!(update, doc! { "$set": { "address": Bson::Null } });
+
+        // Verify staged nested updates are forwarded as usual
+        let update = User::update_builder()
+            .with_address_or_null(|builder| builder.set_city("Paris".to_string()))
+            .build()
+            .unwrap();
+        assert_eq!(update, doc! { "$set": { "address.city": "Paris" } });
+    }
+
+    #[test]
+    fn test_nested_operator_forwarding() {
+        let update = Page::update_builder()
+            .set_title("Home".to_string())
+            .with_stats(|builder| builder.inc_views(1).push_referrers("search".to_string()))
+            .build()
+            .unwrap();
+
+        // Verify every nested operator is forwarded with the field prefix
+        assert_eq!(
+            update,
+            doc! {
+                "$set": { "title": "Home" },
+                "$inc": { "stats.views": 1_i64 },
+                "$push": { "stats.referrers": { "$each": ["search"] } },
+            }
+        );
+    }
+
+    #[test]
+    fn test_current_date() {
+        let update = Stats::update_builder()
+            .set_updated_at_now()
+            .build()
+            .unwrap();
+        assert_eq!(
+            update.get_document("$currentDate").unwrap(),
+            &doc! { "updated_at": true }
+        );
+
+        // Verify the timestamp variant names the BSON type
+        let update = Stats::update_builder()
+            .set_updated_at_timestamp()
+            .build()
+            .unwrap();
+        assert_eq!(
+            update,
+            doc! { "$currentDate": { "updated_at": { "$type": "timestamp" } } }
+        );
+
+        // Verify nested builders forward the operator with the field prefix
+        let update = Page::update_builder()
+            .with_stats(|builder| builder.set_updated_at_now())
+            .build()
+            .unwrap();
+        assert_eq!(
+            update,
+            doc! { "$currentDate": { "stats.updated_at": true } }
+        );
+    }
+
+    #[test]
+    fn test_build_pipeline() {
+        let pipeline = User::update_builder()
+            .set_name("John".to_string())
+            .unset_email()
+            .add_stage(doc! {
+                "$set": { "display": { "$concat": ["$name", " (", "$login_count", ")"] } }
+            })
+            .build_pipeline()
+            .unwrap();
+
+        // Verify typed operations lead as literal stages and raw stages follow
+        assert_eq!(
+            pipeline,
+            vec![
+                doc! { "$set": { "name": { "$literal": "John" } } },
+                doc! { "$unset": ["email"] },
+                doc! {
+                    "$set": { "display": { "$concat": ["$name", " (", "$login_count", ")"] } }
+                },
+            ]
+        );
+
+        // Verify operators without a pipeline stage are rejected
+        assert!(User::update_builder()
+            .push_tags("rust".to_string())
+            .build_pipeline()
+            .is_err());
+    }
+
+    #[test]
+    fn test_empty_operator_blocks_dropped() {
+        let update = User::update_builder()
+            .set_name("John".to_string())
+            .with_operator(|update: &mut Document| {
+                update.insert("$push", Document::new());
+            })
+            .build()
+            .unwrap();
+
+        // Verify empty operator blocks never reach the update
+        assert_eq!(update, doc! { "$set": { "name": "John" } });
+    }
+
+    #[test]
+    fn test_pre_serialized_values() {
+        let name = UserPreSerialized::new("Shared".to_string()).unwrap();
+
+        // Verify the cached value is staged by every builder
+        for _ in 0..2 {
+            let update = User::update_builder()
+                .set_name("Replaced".to_string())
+                .set_name_pre(&name)
+                .build()
+                .unwrap();
+            assert_eq!(update, doc! { "$set": { "name": "Shared" } });
+        }
+        assert_eq!(name.as_bson(), &Bson::String("Shared".to_string()));
+
+        // Verify a later plain setter replaces the cached value
+        let update = User::update_builder()
+            .set_name_pre(&name)
+            .set_name("plain".to_string())
+            .build()
+            .unwrap();
+        assert_eq!(update, doc! { "$set": { "name": "plain" } });
+
+        // Verify a later null setter replaces it too
+        let discount = ListingPreSerialized::new(Some(10)).unwrap();
+        let update = Listing::update_builder()
+            .set_discount_pre(&discount)
+            .set_discount_null()
+            .build()
+            .unwrap();
+        assert_eq!(update, doc! { "$set": { "discount": Bson::Null } });
+    }
+
+    #[test]
+    fn test_history_records_call_order() {
+        let builder = User::update_builder()
+            .with_history()
+            .set_name("First".to_string())
+            .inc_login_count(1)
+            .set_name("Second".to_string())
+            .with_address(|builder| builder.set_city("Oslo".to_string()));
+
+        // Verify every call is kept in order, including superseded ones
+        let calls: Vec<_> = builder
+            .history()
+            .iter()
+            .map(|op| (op.method, op.operator.as_str(), op.path.as_str()))
+            .collect();
+        assert_eq!(
+            calls,
+            [
+                ("set_name", "$set", "name"),
+                ("inc_login_count", "$inc", "login_count"),
+                ("set_name", "$set", "name"),
+                ("with_address", "$set", "address.city"),
+            ]
+        );
+        assert_eq!(
+            builder.history()[0].value,
+            Some(Bson::String("First".to_string()))
+        );
+
+        // Verify the built update only reflects the final state
+        let update = builder.build().unwrap();
+        let set_doc = get_operator_doc(&update, "$set").expect("$set operator should exist");
+        assert_eq!(set_doc.get_str("name").unwrap(), "Second");
+
+        // Verify nothing is recorded unless enabled
+        let builder = User::update_builder().set_name("John".to_string());
+        assert!(builder.history().is_empty());
+
+        // Verify direct nested paths and nulled sub-documents are recorded
+        let builder = User::update_builder()
+            .with_history()
+            .address("zip", "10001")
+            .unwrap()
+            .with_preferences_or_null(|builder| builder);
+        let calls: Vec<_> = builder
+            .history()
+            .iter()
+            .map(|op| (op.method, op.operator.as_str(), op.path.as_str()))
+            .collect();
+        assert_eq!(
+            calls,
+            [
+                ("address", "$set", "address.zip"),
+                ("with_preferences_or_null", "$set", "preferences"),
+            ]
+        );
+
+        // Verify null setters record the operator they emit
+        let builder = ContactCard::update_builder()
+            .with_history()
+            .set_phone_null()
+            .set_fax_null()
+            .set_pager_null();
+        let calls: Vec<_> = builder
+            .history()
+            .iter()
+            .map(|op| (op.method, op.operator.as_str()))
+            .collect();
+        assert_eq!(
+            calls,
+            [("set_phone_null", "$set"), ("set_fax_null", "$unset")]
+        );
+    }
+
+    #[test]
+    fn test_tuple_fields() {
+        let update = Venue::update_builder()
+            .set_location((1.0, 2.0))
+            .set_entrance((3.0, 4.0))
+            .build()
+            .unwrap();
+
+        // Verify plain tuples are arrays and named tuples sub-documents
+        assert_eq!(
+            update,
+            doc! {
+                "$set": {
+                    "location": [1.0, 2.0],
+                    "entrance": { "lng": 3.0, "lat": 4.0 },
+                }
+            }
+        );
+
+        // Verify diff stages tuples in the same form
+        let old = Venue {
+            location: (1.0, 2.0),
+            entrance: (3.0, 4.0),
+        };
+        let new = Venue {
+            location: (1.0, 2.0),
+            entrance: (5.0, 6.0),
+        };
+        let update = VenueUpdateBuilder::diff(&old, &new)
+            .unwrap()
+            .build()
+            .unwrap();
+        assert_eq!(
+            update,
+            doc! { "$set": { "entrance": { "lng": 5.0, "lat": 6.0 } } }
+        );
+    }
+
+    #[test]
+    fn test_set_root() {
+        let update = User::update_builder()
+            .set_name("John".to_string())
+            .set_root(doc! { "email": "john@example.com", "score": 42 })
+            .unwrap()
+            .build()
+            .unwrap();
+
+        // Verify root fields are merged with the typed setters
+        let set_doc = get_operator_doc(&update, "$set").expect("$set operator should exist");
+        assert_eq!(set_doc.get_str("name").unwrap(), "John");
+        assert_eq!(set_doc.get_str("email").unwrap(), "john@example.com");
+        assert_eq!(set_doc.get_i32("score").unwrap(), 42);
+
+        // Verify operator keys are rejected
+        assert!(User::update_builder()
+            .set_root(doc! { "$unset": { "name": "" } })
+            .is_err());
+    }
+
+    #[test]
+    fn test_on_conflict_error() {
+        // Verify typed setters staging a field twice make the build fail
+        let result = Invoice::update_builder()
+            .set_number("A-1".to_string())
+            .set_number("A-2".to_string())
+            .build();
+        assert_eq!(
+            result
+                .unwrap_err()
+                .get_custom::<String>()
+                .map(String::as_str),
+            Some("`number` is staged more than once")
+        );
+
+        // Verify path methods fail immediately
+        assert!(Invoice::update_builder()
+            .set_field("total", 1)
+            .unwrap()
+            .set_field("total", 2)
+            .is_err());
+
+        // Verify a path method after a typed setter for the same field fails the build
+        let result = Invoice::update_builder()
+            .set_number("A-1".to_string())
+            .set_field("number", "A-2")
+            .unwrap()
+            .build();
+        assert_eq!(
+            result
+                .unwrap_err()
+                .get_custom::<String>()
+                .map(String::as_str),
+            Some("`number` is staged more than once")
+        );
+        let (update, failures) = Invoice::update_builder()
+            .set_number("A-1".to_string())
+            .set_many(&[("number", Bson::from("A-2"))])
+            .build_lossy();
+        assert!(update.is_empty());
+        assert_eq!(failures[0].0, "number");
+
+        // Verify direct nested paths fail immediately
+        assert!(Invoice::update_builder()
+            .billing_address("city", "a")
+            .unwrap()
+            .billing_address("city", "b")
+            .is_err());
+
+        // Verify nested builders staging the same path make the build fail
+        let result = Invoice::update_builder()
+            .with_billing_address(|builder| builder.set_city("a".to_string()))
+            .with_billing_address(|builder| builder.set_city("b".to_string()))
+            .build();
+        assert_eq!(
+            result
+                .unwrap_err()
+                .get_custom::<String>()
+                .map(String::as_str),
+            Some("`billing_address.city` is staged more than once")
+        );
+
+        // Verify a setter and an unset of the same field make the build fail
+        let result = Invoice::update_builder()
+            .set_memo("a".to_string())
+            .unset_memo()
+            .build();
+        assert_eq!(
+            result
+                .unwrap_err()
+                .get_custom::<String>()
+                .map(String::as_str),
+            Some("`memo` is staged more than once")
+        );
+        assert!(Invoice::update_builder()
+            .unset_memo()
+            .set_memo("a".to_string())
+            .build()
+            .is_err());
+
+        // Verify the null staged for an empty nested builder is checked too
+        let result = Invoice::update_builder()
+            .set_field("billing_address", "x")
+            .unwrap()
+            .with_billing_address_or_null(|builder| builder)
+            .build();
+        assert_eq!(
+            result
+                .unwrap_err()
+                .get_custom::<String>()
+                .map(String::as_str),
+            Some("`billing_address` is staged more than once")
+        );
+        assert!(Invoice::update_builder()
+            .with_billing_address_or_null(|builder| builder)
+            .with_billing_address_or_null(|builder| builder)
+            .build()
+            .is_err());
+
+        // Verify distinct fields are unaffected
+        let update = Invoice::update_builder()
+            .set_number("A-1".to_string())
+            .set_total(100)
+            .build()
+            .unwrap();
+        assert_eq!(
+            update,
+            doc! { "$set": { "number": "A-1", "total": 100_i64 } }
+        );
+
+        // Verify the default keeps the last value
+        let update = User::update_builder()
+            .set_name("First".to_string())
+            .set_name("Second".to_string())
+            .build()
+            .unwrap();
+        assert_eq!(update, doc! { "$set": { "name": "Second" } });
+    }
+
+    #[test]
+    fn test_merge_builders() {
+        let profile = User::update_builder()
+            .set_name("John".to_string())
+            .push_tags("rust".to_string())
+            .set_field("settings.theme", "dark")
+            .unwrap();
+        let activity = User::update_builder()
+            .set_login_count(31)
+            .push_tags("mongodb".to_string())
+            .set_field("settings.theme", "light")
+            .unwrap();
+
+        let update = profile.merge(activity).build().unwrap();
+
+        // Verify both builders' fields are set, the right-hand path winning
+        assert_eq!(
+            update.get_document("$set").unwrap(),
+            &doc! { "name": "John", "login_count": 31, "settings.theme": "light" }
+        );
+
+        // Verify pushes to the same field are combined into a single $each
+        assert_eq!(
+            update.get_document("$push").unwrap(),
+            &doc! { "tags": { "$each": ["rust", "mongodb"] } }
+        );
+
+        // Verify fields staged by both builders are conflicts when configured
+        assert!(Invoice::update_builder()
+            .set_number("A-1".to_string())
+            .merge(Invoice::update_builder().set_number("A-2".to_string()))
+            .build()
+            .is_err());
+    }
+
+    #[test]
+    fn test_mul() {
+        let update = Inventory::update_builder()
+            .inc_stock(-1)
+            .mul_price(1.1)
+            .build()
+            .unwrap();
+
+        // Verify $inc and $mul are emitted side by side
+        assert_eq!(update.get_document("$mul").unwrap(), &doc! { "price": 1.1 });
+        assert_eq!(update.get_document("$inc").unwrap(), &doc! { "stock": -1 });
+    }
+
+    #[test]
+    fn test_bitwise_updates() {
+        let update = User::update_builder().bit_or_flags(4).build().unwrap();
+        assert_eq!(update, doc! { "$bit": { "flags": { "or": 4 } } });
+
+        // Verify only the last bitwise operation on a field is kept
+        let update = User::update_builder()
+            .bit_and_flags(6)
+            .bit_xor_flags(1)
+            .build()
+            .unwrap();
+        assert_eq!(update, doc! { "$bit": { "flags": { "xor": 1 } } });
+    }
+
+    #[test]
+    fn test_min_max() {
+        // Verify $max stages the candidate high score
+        let update = User::update_builder().max_high_score(500).build().unwrap();
+        assert_eq!(update, doc! { "$max": { "high_score": 500 } });
+
+        // Verify $min and other operators are emitted side by side
+        let update = User::update_builder()
+            .min_high_score(10)
+            .inc_login_count(1)
+            .build()
+            .unwrap();
+        assert_eq!(
+            update,
+            doc! { "$inc": { "login_count": 1 }, "$min": { "high_score": 10 } }
+        );
+    }
+
+    #[test]
+    fn test_unset_fields() {
+        let update = User::update_builder()
+            .set_name("John".to_string())
+            .unset_email()
+            .build()
+            .unwrap();
+
+        // Verify $set and $unset are emitted side by side
+        let set_doc = get_operator_doc(&update, "$set").expect("$set operator should exist");
+        assert_eq!(set_doc, &doc! { "name": "John" });
+        let unset_doc = get_operator_doc(&update, "$unset").expect("$unset operator should exist");
+        assert_eq!(unset_doc, &doc! { "email": "" });
+
+        // Verify the later of a setter and an unset wins
+        let update = User::update_builder()
+            .set_email("a".to_string())
+            .unset_email()
+            .build()
+            .unwrap();
+        assert_eq!(update, doc! { "$unset": { "email": "" } });
+        let update = User::update_builder()
+            .unset_email()
+            .set_email("a".to_string())
+            .build()
+            .unwrap();
+        assert_eq!(update, doc! { "$set": { "email": "a" } });
+        let update = User::update_builder()
+            .set_field("email", "a")
+            .unwrap()
+            .unset_email()
+            .build()
+            .unwrap();
+        assert_eq!(update, doc! { "$unset": { "email": "" } });
+    }
+
+    #[test]
+    fn test_serde_renamed_keys() {
+        let update = Customer::update_builder()
+            .set_zip_code("75001".to_string())
+            .push_phone_numbers("555-0100".to_string())
+            .with_postal_address(|builder| {
+                builder
+                    .set_street_name("Rue de Rivoli".to_string())
+                    .set_zip_code("75001".to_string())
+            })
+            .build()
+            .unwrap();
+
+        // Verify per-field renames, rename_all and nested renames shape the keys
+        let set_doc = get_operator_doc(&update, "$set").expect("$set operator should exist");
+        assert_eq!(set_doc.get_str("zipCode").unwrap(), "75001");
+        assert_eq!(
+            set_doc.get_str("postalAddress.streetName").unwrap(),
+            "Rue de Rivoli"
+        );
+        assert_eq!(set_doc.get_str("postalAddress.zip").unwrap(), "75001");
+        let push_doc = get_operator_doc(&update, "$push").expect("$push operator should exist");
+        assert!(push_doc.contains_key("phoneNumbers"));
+
+        // Verify the keys match those serde stores
+        let customer = Customer {
+            phone_numbers: Vec::new(),
+            zip_code: String::new(),
+            postal_address: PostalAddress {
+                street_name: String::new(),
+                zip_code: String::new(),
+            },
+        };
+        let stored = bson::to_document(&customer).unwrap();
+        assert_eq!(
+            stored.keys().collect::<Vec<_>>(),
+            ["phoneNumbers", "zipCode", "postalAddress"]
+        );
+    }
 }
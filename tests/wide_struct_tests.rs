@@ -0,0 +1,398 @@
+// Wide documents must derive without raising `recursion_limit`
+#[cfg(test)]
+mod tests {
+    use bson::doc;
+    use mongo_derive::{MongoFilter, MongoOperations};
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Debug, Serialize, Deserialize, Clone, MongoOperations, MongoFilter)]
+    struct Analytics {
+        #[mongo_ops(set)]
+        metric_000: String,
+
+        #[mongo_ops(set, inc)]
+        metric_001: i64,
+
+        #[mongo_ops(set, push, pull)]
+        metric_002: Vec<String>,
+
+        #[mongo_ops(set)]
+        metric_003: Option<bool>,
+
+        #[mongo_ops(set)]
+        metric_004: String,
+
+        #[mongo_ops(set, inc)]
+        metric_005: i64,
+
+        #[mongo_ops(set, push, pull)]
+        metric_006: Vec<String>,
+
+        #[mongo_ops(set)]
+        metric_007: Option<bool>,
+
+        #[mongo_ops(set)]
+        metric_008: String,
+
+        #[mongo_ops(set, inc)]
+        metric_009: i64,
+
+        #[mongo_ops(set, push, pull)]
+        metric_010: Vec<String>,
+
+        #[mongo_ops(set)]
+        metric_011: Option<bool>,
+
+        #[mongo_ops(set)]
+        metric_012: String,
+
+        #[mongo_ops(set, inc)]
+        metric_013: i64,
+
+        #[mongo_ops(set, push, pull)]
+        metric_014: Vec<String>,
+
+        #[mongo_ops(set)]
+        metric_015: Option<bool>,
+
+        #[mongo_ops(set)]
+        metric_016: String,
+
+        #[mongo_ops(set, inc)]
+        metric_017: i64,
+
+        #[mongo_ops(set, push, pull)]
+        metric_018: Vec<String>,
+
+        #[mongo_ops(set)]
+        metric_019: Option<bool>,
+
+        #[mongo_ops(set)]
+        metric_020: String,
+
+        #[mongo_ops(set, inc)]
+        metric_021: i64,
+
+        #[mongo_ops(set, push, pull)]
+        metric_022: Vec<String>,
+
+        #[mongo_ops(set)]
+        metric_023: Option<bool>,
+
+        #[mongo_ops(set)]
+        metric_024: String,
+
+        #[mongo_ops(set, inc)]
+        metric_025: i64,
+
+        #[mongo_ops(set, push, pull)]
+        metric_026: Vec<String>,
+
+        #[mongo_ops(set)]
+        metric_027: Option<bool>,
+
+        #[mongo_ops(set)]
+        metric_028: String,
+
+        #[mongo_ops(set, inc)]
+        metric_029: i64,
+
+        #[mongo_ops(set, push, pull)]
+        metric_030: Vec<String>,
+
+        #[mongo_ops(set)]
+        metric_031: Option<bool>,
+
+        #[mongo_ops(set)]
+        metric_032: String,
+
+        #[mongo_ops(set, inc)]
+        metric_033: i64,
+
+        #[mongo_ops(set, push, pull)]
+        metric_034: Vec<String>,
+
+        #[mongo_ops(set)]
+        metric_035: Option<bool>,
+
+        #[mongo_ops(set)]
+        metric_036: String,
+
+        #[mongo_ops(set, inc)]
+        metric_037: i64,
+
+        #[mongo_ops(set, push, pull)]
+        metric_038: Vec<String>,
+
+        #[mongo_ops(set)]
+        metric_039: Option<bool>,
+
+        #[mongo_ops(set)]
+        metric_040: String,
+
+        #[mongo_ops(set, inc)]
+        metric_041: i64,
+
+        #[mongo_ops(set, push, pull)]
+        metric_042: Vec<String>,
+
+        #[mongo_ops(set)]
+        metric_043: Option<bool>,
+
+        #[mongo_ops(set)]
+        metric_044: String,
+
+        #[mongo_ops(set, inc)]
+        metric_045: i64,
+
+        #[mongo_ops(set, push, pull)]
+        metric_046: Vec<String>,
+
+        #[mongo_ops(set)]
+        metric_047: Option<bool>,
+
+        #[mongo_ops(set)]
+        metric_048: String,
+
+        #[mongo_ops(set, inc)]
+        metric_049: i64,
+
+        #[mongo_ops(set, push, pull)]
+        metric_050: Vec<String>,
+
+        #[mongo_ops(set)]
+        metric_051: Option<bool>,
+
+        #[mongo_ops(set)]
+        metric_052: String,
+
+        #[mongo_ops(set, inc)]
+        metric_053: i64,
+
+        #[mongo_ops(set, push, pull)]
+        metric_054: Vec<String>,
+
+        #[mongo_ops(set)]
+        metric_055: Option<bool>,
+
+        #[mongo_ops(set)]
+        metric_056: String,
+
+        #[mongo_ops(set, inc)]
+        metric_057: i64,
+
+        #[mongo_ops(set, push, pull)]
+        metric_058: Vec<String>,
+
+        #[mongo_ops(set)]
+        metric_059: Option<bool>,
+
+        #[mongo_ops(set)]
+        metric_060: String,
+
+        #[mongo_ops(set, inc)]
+        metric_061: i64,
+
+        #[mongo_ops(set, push, pull)]
+        metric_062: Vec<String>,
+
+        #[mongo_ops(set)]
+        metric_063: Option<bool>,
+
+        #[mongo_ops(set)]
+        metric_064: String,
+
+        #[mongo_ops(set, inc)]
+        metric_065: i64,
+
+        #[mongo_ops(set, push, pull)]
+        metric_066: Vec<String>,
+
+        #[mongo_ops(set)]
+        metric_067: Option<bool>,
+
+        #[mongo_ops(set)]
+        metric_068: String,
+
+        #[mongo_ops(set, inc)]
+        metric_069: i64,
+
+        #[mongo_ops(set, push, pull)]
+        metric_070: Vec<String>,
+
+        #[mongo_ops(set)]
+        metric_071: Option<bool>,
+
+        #[mongo_ops(set)]
+        metric_072: String,
+
+        #[mongo_ops(set, inc)]
+        metric_073: i64,
+
+        #[mongo_ops(set, push, pull)]
+        metric_074: Vec<String>,
+
+        #[mongo_ops(set)]
+        metric_075: Option<bool>,
+
+        #[mongo_ops(set)]
+        metric_076: String,
+
+        #[mongo_ops(set, inc)]
+        metric_077: i64,
+
+        #[mongo_ops(set, push, pull)]
+        metric_078: Vec<String>,
+
+        #[mongo_ops(set)]
+        metric_079: Option<bool>,
+
+        #[mongo_ops(set)]
+        metric_080: String,
+
+        #[mongo_ops(set, inc)]
+        metric_081: i64,
+
+        #[mongo_ops(set, push, pull)]
+        metric_082: Vec<String>,
+
+        #[mongo_ops(set)]
+        metric_083: Option<bool>,
+
+        #[mongo_ops(set)]
+        metric_084: String,
+
+        #[mongo_ops(set, inc)]
+        metric_085: i64,
+
+        #[mongo_ops(set, push, pull)]
+        metric_086: Vec<String>,
+
+        #[mongo_ops(set)]
+        metric_087: Option<bool>,
+
+        #[mongo_ops(set)]
+        metric_088: String,
+
+        #[mongo_ops(set, inc)]
+        metric_089: i64,
+
+        #[mongo_ops(set, push, pull)]
+        metric_090: Vec<String>,
+
+        #[mongo_ops(set)]
+        metric_091: Option<bool>,
+
+        #[mongo_ops(set)]
+        metric_092: String,
+
+        #[mongo_ops(set, inc)]
+        metric_093: i64,
+
+        #[mongo_ops(set, push, pull)]
+        metric_094: Vec<String>,
+
+        #[mongo_ops(set)]
+        metric_095: Option<bool>,
+
+        #[mongo_ops(set)]
+        metric_096: String,
+
+        #[mongo_ops(set, inc)]
+        metric_097: i64,
+
+        #[mongo_ops(set, push, pull)]
+        metric_098: Vec<String>,
+
+        #[mongo_ops(set)]
+        metric_099: Option<bool>,
+
+        #[mongo_ops(set)]
+        metric_100: String,
+
+        #[mongo_ops(set, inc)]
+        metric_101: i64,
+
+        #[mongo_ops(set, push, pull)]
+        metric_102: Vec<String>,
+
+        #[mongo_ops(set)]
+        metric_103: Option<bool>,
+
+        #[mongo_ops(set)]
+        metric_104: String,
+
+        #[mongo_ops(set, inc)]
+        metric_105: i64,
+
+        #[mongo_ops(set, push, pull)]
+        metric_106: Vec<String>,
+
+        #[mongo_ops(set)]
+        metric_107: Option<bool>,
+
+        #[mongo_ops(set)]
+        metric_108: String,
+
+        #[mongo_ops(set, inc)]
+        metric_109: i64,
+
+        #[mongo_ops(set, push, pull)]
+        metric_110: Vec<String>,
+
+        #[mongo_ops(set)]
+        metric_111: Option<bool>,
+
+        #[mongo_ops(set)]
+        metric_112: String,
+
+        #[mongo_ops(set, inc)]
+        metric_113: i64,
+
+        #[mongo_ops(set, push, pull)]
+        metric_114: Vec<String>,
+
+        #[mongo_ops(set)]
+        metric_115: Option<bool>,
+
+        #[mongo_ops(set)]
+        metric_116: String,
+
+        #[mongo_ops(set, inc)]
+        metric_117: i64,
+
+        #[mongo_ops(set, push, pull)]
+        metric_118: Vec<String>,
+
+        #[mongo_ops(set)]
+        metric_119: Option<bool>,
+    }
+
+    #[test]
+    fn test_wide_struct() {
+        let update = Analytics::update_builder()
+            .set_metric_000("first".to_string())
+            .inc_metric_117(3)
+            .push_metric_118("last".to_string())
+            .build()
+            .unwrap();
+
+        // Verify methods are generated for fields at both ends of the struct
+        assert_eq!(
+            update,
+            doc! {
+                "$set": { "metric_000": "first" },
+                "$push": { "metric_118": { "$each": ["last"] } },
+                "$inc": { "metric_117": 3_i64 },
+            }
+        );
+        assert_eq!(
+            AnalyticsUpdateBuilder::describe().last(),
+            Some(&("set_metric_119_pre", "$set"))
+        );
+
+        // Verify the filter builder scales the same way
+        let filter = Analytics::filter().gt_metric_117(10).build().unwrap();
+        assert_eq!(filter, doc! { "metric_117": { "$gt": 10_i64 } });
+    }
+}